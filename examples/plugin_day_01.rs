@@ -0,0 +1,88 @@
+//! An alternative day 1 solver as a runner plugin, counting the right list once
+//! for part B instead of scanning it for every left number.
+//!
+//! ```bash
+//! cargo build --release --example plugin_day_01
+//! mkdir -p plugins && cp target/release/examples/libplugin_day_01.so plugins/
+//! cargo run --release -- --day 1
+//! ```
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::os::raw::c_int;
+use std::panic::{self, AssertUnwindSafe};
+use std::{slice, str};
+
+use lib::day_01::parse;
+use lib::ffi::{
+    write_buffer, AocBuffer, AOC_ERR_NULL, AOC_ERR_PANIC, AOC_ERR_PARSE, AOC_ERR_PART,
+    AOC_ERR_UTF8, AOC_OK,
+};
+use lib::plugin::{AocPluginDay, AocRegisterFn, AOC_PLUGIN_ABI_VERSION};
+
+#[no_mangle]
+pub extern "C" fn aoc_plugin_abi_version() -> u32 {
+    AOC_PLUGIN_ABI_VERSION
+}
+
+/// # Safety
+///
+/// `register` must be the runner's registration callback for `ctx`.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_plugin_register(register: AocRegisterFn, ctx: *mut c_void) {
+    let day = AocPluginDay {
+        day: 1,
+        name: c"counting".as_ptr(),
+        solve,
+    };
+    register(ctx, &day);
+}
+
+unsafe extern "C" fn solve(
+    part: u32,
+    input_ptr: *const u8,
+    len: usize,
+    out_buf: *mut AocBuffer,
+) -> c_int {
+    if input_ptr.is_null() || out_buf.is_null() || (*out_buf).data.is_null() {
+        return AOC_ERR_NULL;
+    }
+    let input = slice::from_raw_parts(input_ptr, len);
+    // a panic must not unwind into the runner
+    let (status, text) = match panic::catch_unwind(AssertUnwindSafe(|| run(part, input))) {
+        Ok(result) => result,
+        Err(_) => (AOC_ERR_PANIC, "solver panicked".to_string()),
+    };
+    match write_buffer(&mut *out_buf, &text) {
+        Ok(()) => status,
+        Err(e) => e,
+    }
+}
+
+fn run(part: u32, input: &[u8]) -> (c_int, String) {
+    match str::from_utf8(input) {
+        Ok(input) => match (part, parse(input)) {
+            (1, Ok((ls, rs))) => (AOC_OK, distance(ls, rs).to_string()),
+            (2, Ok((ls, rs))) => (AOC_OK, similarity(&ls, &rs).to_string()),
+            (1 | 2, Err(e)) => (AOC_ERR_PARSE, e.in_day(1).to_string()),
+            _ => (AOC_ERR_PART, format!("unknown part {}", part)),
+        },
+        Err(e) => (AOC_ERR_UTF8, e.to_string()),
+    }
+}
+
+// widened like day_01, so any i32 lists add up without overflowing
+fn distance(mut ls: Vec<i32>, mut rs: Vec<i32>) -> u64 {
+    ls.sort_unstable();
+    rs.sort_unstable();
+    ls.iter().zip(rs).map(|(&l, r)| l.abs_diff(r) as u64).sum()
+}
+
+fn similarity(ls: &[i32], rs: &[i32]) -> i64 {
+    let mut counts: HashMap<i32, i64> = HashMap::new();
+    for &r in rs {
+        *counts.entry(r).or_default() += 1;
+    }
+    ls.iter()
+        .map(|&l| l as i64 * counts.get(&l).unwrap_or(&0))
+        .sum()
+}
@@ -0,0 +1,85 @@
+#ifndef AOC2024_H
+#define AOC2024_H
+
+/* Generated by cbindgen from src/ffi.rs and src/plugin.rs, do not edit. Regenerate with AOC_BLESS=1 cargo test --test ffi */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The only year this library solves.
+#define AOC_YEAR 2024
+
+#define AOC_OK 0
+
+// `input_ptr` or `out_buf` (or its data) is null.
+#define AOC_ERR_NULL 1
+
+// The input is not valid UTF-8.
+#define AOC_ERR_UTF8 2
+
+#define AOC_ERR_YEAR 3
+
+// Not a day of December, or a day without a solver yet.
+#define AOC_ERR_DAY 4
+
+// `part` is not 1 or 2.
+#define AOC_ERR_PART 5
+
+// The input could not be parsed, the message holds the line and column.
+#define AOC_ERR_PARSE 6
+
+// The input parsed but has no answer.
+#define AOC_ERR_SOLVE 7
+
+// The buffer is too small, `len` holds the length needed without the NUL.
+#define AOC_ERR_BUFFER 8
+
+#define AOC_ERR_PANIC 9
+
+// Bumped whenever the plugin types or entry points change.
+#define AOC_PLUGIN_ABI_VERSION 1
+
+// Caller owned output buffer, `aoc_solve` writes a NUL terminated string to
+// `data` and its length without the NUL to `len`.
+typedef struct AocBuffer {
+  char *data;
+  size_t capacity;
+  size_t len;
+} AocBuffer;
+
+// Solves `part` (1 or 2) for the `len` bytes of input at `input_ptr`, writing the
+// answer (or an error message) to `out_buf` and returning an `AOC_` status code.
+typedef int (*AocPluginSolveFn)(uint32_t part,
+                                const uint8_t *input_ptr,
+                                size_t len,
+                                struct AocBuffer *out_buf);
+
+// One solver registered by a plugin, `name` tells it apart from the built in solver.
+typedef struct AocPluginDay {
+  uint32_t day;
+  // NUL terminated, copied during registration.
+  const char *name;
+  AocPluginSolveFn solve;
+} AocPluginDay;
+
+// Passed to `aoc_plugin_register`, to be called with its `ctx` for every day.
+typedef void (*AocRegisterFn)(void *ctx, const struct AocPluginDay *day);
+
+// Solves `part` (1 or 2) of `day` for the `len` bytes of input at `input_ptr`,
+// returns one of the `AOC_` status codes.
+//
+// # Safety
+//
+// `input_ptr` must point to `len` readable bytes, `out_buf` to a valid `AocBuffer`
+// whose `data` points to `capacity` writable bytes.
+int aoc_solve(uint32_t year,
+              uint32_t day,
+              uint32_t part,
+              const uint8_t *input_ptr,
+              size_t len,
+              struct AocBuffer *out_buf);
+
+#endif  /* AOC2024_H */
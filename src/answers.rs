@@ -0,0 +1,223 @@
+//! Expected answers for `aoc2024 verify`.
+//!
+//! The answers file (`data/answers.txt`, or `AOC_ANSWERS_FILE`) holds one
+//! `day_XX_a = VALUE` line per part. So expectations can be committed without
+//! publishing answers, a value may instead be stored as `sha256:SALT:HASH`, the
+//! SHA-256 of the salt followed by the answer. Each answer gets its own random
+//! salt, so equal answers don't give each other away. This keeps answers out of
+//! sight, it is no protection against someone willing to brute force small numbers.
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use sha2::{Digest, Sha256};
+
+use crate::api::answer_value;
+use crate::common::hex;
+use crate::error::{ParseError, ParseResult};
+
+/// Shown instead of an answer in `--redact` output.
+pub const REDACTED: &str = "********";
+
+pub fn answers_file() -> PathBuf {
+    env::var_os("AOC_ANSWERS_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/data/answers.txt")))
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Expected {
+    Plain(String),
+    Hashed { salt: String, hash: String },
+}
+
+impl Expected {
+    /// Hashes `value` with a fresh random salt.
+    pub fn hashed(value: &str) -> Self {
+        let mut salt = [0u8; 8];
+        OsRng.fill_bytes(&mut salt);
+        let salt = hex(&salt);
+        let hash = salted_hash(&salt, value);
+        Expected::Hashed { salt, hash }
+    }
+
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            Expected::Plain(v) => v == value,
+            Expected::Hashed { salt, hash } => *hash == salted_hash(salt, value),
+        }
+    }
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Plain(v) => write!(f, "{}", v),
+            Expected::Hashed { salt, hash } => write!(f, "sha256:{}:{}", salt, hash),
+        }
+    }
+}
+
+pub fn salted_hash(salt: &str, value: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    hasher.update(value.as_bytes());
+    hex(&hasher.finalize())
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// No expected answer for this part.
+    Unknown,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // pad so the status lines up in columns
+        f.pad(match self {
+            Status::Pass => "ok",
+            Status::Fail => "FAIL",
+            Status::Unknown => "?",
+        })
+    }
+}
+
+/// Expected answers by label, e.g. `day_01_a`.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    entries: BTreeMap<String, Expected>,
+}
+
+impl Answers {
+    /// Loads `path`, a missing file has no answers.
+    pub fn load(path: &Path) -> io::Result<Answers> {
+        match fs::read_to_string(path) {
+            Ok(text) => Answers::parse(&text)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn parse(text: &str) -> ParseResult<Answers> {
+        let mut entries = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((label, value)) = line.split_once(" = ") else {
+                return Err(ParseError::new(i + 1, 1, "expected 'day_XX_a = answer'"));
+            };
+            let expected = match value.strip_prefix("sha256:") {
+                Some(rest) => match rest.split_once(':') {
+                    Some((salt, hash)) if hash.len() == 64 => Expected::Hashed {
+                        salt: salt.to_string(),
+                        hash: hash.to_string(),
+                    },
+                    _ => return Err(ParseError::new(i + 1, 1, "expected 'sha256:SALT:HASH'")),
+                },
+                None => Expected::Plain(value.to_string()),
+            };
+            entries.insert(label.to_string(), expected);
+        }
+        Ok(Answers { entries })
+    }
+
+    pub fn get(&self, label: &str) -> Option<&Expected> {
+        self.entries.get(label)
+    }
+
+    /// Checks a solver answer, `day_01_a = 11`, against its expected answer.
+    pub fn check(&self, answer: &str) -> Status {
+        match self.get(label(answer)) {
+            Some(expected) if expected.matches(answer_value(answer)) => Status::Pass,
+            Some(_) => Status::Fail,
+            None => Status::Unknown,
+        }
+    }
+
+    /// Stores a solver answer as the expected one, as a salted hash when `hashed`.
+    pub fn record(&mut self, answer: &str, hashed: bool) {
+        let value = answer_value(answer);
+        let expected = if hashed {
+            Expected::hashed(value)
+        } else {
+            Expected::Plain(value.to_string())
+        };
+        self.entries.insert(label(answer).to_string(), expected);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (label, expected) in &self.entries {
+            writeln!(f, "{} = {}", label, expected)?;
+        }
+        Ok(())
+    }
+}
+
+fn label(answer: &str) -> &str {
+    answer.split_once(" = ").map_or(answer, |(label, _)| label)
+}
+
+/// Replaces the value of a solver answer, `day_01_a = 11` becomes `day_01_a = ********`.
+pub fn redact(answer: &str) -> String {
+    format!("{} = {}", label(answer), REDACTED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashed_test() {
+        let a = Expected::hashed("11");
+        let b = Expected::hashed("11");
+        assert!(a.matches("11") && b.matches("11"));
+        assert!(!a.matches("12"));
+        // a fresh salt per answer
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn parse_test() {
+        let mut answers = Answers::default();
+        answers.record("day_01_a = 11", true);
+        answers.record("day_01_b = 31", false);
+        let answers = Answers::parse(&answers.to_string()).unwrap();
+        assert_eq!(2, answers.len());
+        assert_eq!(Status::Pass, answers.check("day_01_a = 11"));
+        assert_eq!(Status::Fail, answers.check("day_01_a = 12"));
+        assert_eq!(Status::Pass, answers.check("day_01_b = 31"));
+        assert_eq!(Status::Unknown, answers.check("day_02_a = 2"));
+        assert_eq!(
+            Some(&Expected::Plain("31".to_string())),
+            answers.get("day_01_b")
+        );
+
+        let e = Answers::parse("# comment\n\nday_01_a = sha256:00:beef\n").unwrap_err();
+        assert_eq!(3, e.line);
+    }
+
+    #[test]
+    fn redact_test() {
+        assert_eq!("day_01_a = ********", redact("day_01_a = 11"));
+    }
+}
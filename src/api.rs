@@ -0,0 +1,119 @@
+//! Solving a single part of any day from an input string.
+//!
+//! This is the entry point for code embedding the solvers. Each `day_XX` module
+//! also exposes its `parse` function and the model it returns, together with the
+//! `day_XX_a`/`day_XX_b` functions that solve a part from the parsed model.
+use std::fmt;
+use std::str::FromStr;
+
+use crate::days::get_day;
+use crate::error::{AocError, Result};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Part {
+    pub fn as_char(self) -> char {
+        match self {
+            Part::A => 'a',
+            Part::B => 'b',
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_char())
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    /// Accepts `a`/`b` as well as `1`/`2`.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "a" | "1" => Ok(Part::A),
+            "b" | "2" => Ok(Part::B),
+            _ => Err(format!("unknown part '{}', expected a or b", s)),
+        }
+    }
+}
+
+/// The answer to one part of one day, `value` is the bare answer without a label.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Answer {
+    pub day: u8,
+    pub part: Part,
+    pub value: String,
+}
+
+impl Answer {
+    /// The label the solvers use, e.g. `day_01_a`.
+    pub fn label(&self) -> String {
+        format!("day_{:02}_{}", self.day, self.part)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {}", self.label(), self.value)
+    }
+}
+
+/// Solves `part` of `day` for `input`.
+///
+/// ```
+/// use lib::{solve, Part};
+///
+/// let answer = solve(1, Part::B, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap();
+/// assert_eq!("31", answer.value);
+/// assert_eq!("day_01_b = 31", answer.to_string());
+/// ```
+pub fn solve(day: u8, part: Part, input: &str) -> Result<Answer> {
+    let Some(d) = get_day(day) else {
+        return Err(AocError::new(day, "no solver for this day"));
+    };
+    let f = match part {
+        Part::A => d.part_a,
+        Part::B => d.part_b,
+    };
+    let answer = f(input)?;
+    Ok(Answer {
+        day,
+        part,
+        value: answer_value(&answer).to_string(),
+    })
+}
+
+/// Strips the `day_XX_a = ` label the solvers put in front of their answers.
+pub fn answer_value(answer: &str) -> &str {
+    answer.rsplit(" = ").next().unwrap_or(answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_from_str_test() {
+        assert_eq!(Ok(Part::A), "a".parse());
+        assert_eq!(Ok(Part::B), "2".parse());
+        assert!("c".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn answer_value_test() {
+        assert_eq!("11", answer_value("day_01_a = 11"));
+        assert_eq!("11", answer_value("11"));
+    }
+
+    #[test]
+    fn unknown_day_test() {
+        let e = solve(26, Part::A, "").unwrap_err();
+        assert_eq!("day_26: no solver for this day", e.to_string());
+    }
+}
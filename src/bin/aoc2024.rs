@@ -1,79 +1,830 @@
 use std::collections::HashMap;
-use std::time::Instant;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use rayon::prelude::*;
 
+use lib::answers::{answers_file, redact, Answers, Status};
+use lib::cache::{build_fingerprint, cache_file, input_hash, Cache};
+use lib::cold;
+use lib::common::render;
 use lib::common::Solution;
-use lib::day_01::solve_day_01;
-use lib::day_02::solve_day_02;
-use lib::day_03::solve_day_03;
-use lib::day_04::solve_day_04;
-use lib::day_05::solve_day_05;
-use lib::day_06::solve_day_06;
-use lib::day_07::solve_day_07;
-use lib::day_08::solve_day_08;
-use lib::day_09::solve_day_09;
-use lib::day_10::solve_day_10;
-use lib::day_11::solve_day_11;
+use lib::days::{Day, DAYS};
+use lib::error::{AocError, Result};
+use lib::examples::{
+    examples_dir, load_day_examples, load_examples, next_name, run_example, write_example,
+};
+use lib::history::{self, expected_durations, history_file, longest_first, Run};
+use lib::input;
+use lib::leaderboard::{self, Leaderboard};
+use lib::plugin::{plugin_dir, PluginDay, Plugins};
+use lib::puzzle::{self, Style};
+use lib::report::{self, DayRow};
+use lib::viz::{self, Format};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
     /// Run all AOC 2024 days in parallel
-    #[arg(short, long, action)]
+    #[arg(short, long, global = true, action)]
     par: bool,
 
     /// Run one AOC day
     #[arg(short, long, default_value = None)]
     day: Option<u8>,
+
+    /// Serve answers from the cache when the input and build are unchanged
+    #[arg(long, action)]
+    cached: bool,
+
+    /// Run every day in a fresh process and compare its cold timings to warm in-process ones
+    #[arg(long, action, conflicts_with_all = ["par", "cached"])]
+    cold: bool,
+
+    /// Solve one day as a child of a --cold run
+    #[arg(long, hide = true)]
+    cold_child: Option<u8>,
+
+    /// Hide answers, still showing timings and pass/fail
+    #[arg(long, global = true, action)]
+    redact: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+enum Command {
+    /// Encrypt data/day_XX.txt inputs to day_XX.txt.enc, creating a key file if needed
+    Encrypt {
+        /// Delete the plain text inputs once encrypted
+        #[arg(long, action)]
+        remove_plain: bool,
+    },
+
+    /// Decrypt data/day_XX.txt.enc inputs back to day_XX.txt
+    Decrypt {
+        /// Delete the encrypted inputs once decrypted
+        #[arg(long, action)]
+        remove_encrypted: bool,
+    },
+
+    /// Run every day against the example files in data/examples and report pass/fail
+    Examples {
+        /// Only run the examples of one AOC day
+        #[arg(short, long, default_value = None)]
+        day: Option<u8>,
+    },
+
+    /// Check the answers of every day against data/answers.txt
+    Verify {
+        /// Only verify one AOC day
+        #[arg(short, long, default_value = None)]
+        day: Option<u8>,
+
+        /// Store new and changed answers as the expected ones
+        #[arg(long, action)]
+        record: bool,
+
+        /// Record answers in plain text instead of as salted hashes
+        #[arg(long, action, requires = "record")]
+        plain: bool,
+    },
+
+    /// Show the puzzle description of a day, fetched once and cached
+    Puzzle {
+        /// The AOC day
+        day: u8,
+
+        /// Render Markdown instead of plain text
+        #[arg(short, long, action)]
+        markdown: bool,
+
+        /// Fetch the page again, e.g. once part two is unlocked
+        #[arg(long, action)]
+        refresh: bool,
+
+        /// Site to fetch from, defaults to AOC_BASE_URL or https://adventofcode.com
+        #[arg(long)]
+        base_url: Option<String>,
+
+        /// Wrap plain text paragraphs at this width
+        #[arg(long, default_value_t = 80)]
+        width: usize,
+    },
+
+    /// Turn the example of a cached puzzle page into data/examples files and run them
+    Extract {
+        /// The AOC day
+        day: u8,
+
+        /// Use this code block (1-based) instead of asking which one is the example
+        #[arg(short, long)]
+        block: Option<usize>,
+
+        /// Example name, defaults to the next free number
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Site to fetch from when the page isn't cached yet
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+
+    /// Show star times, part 2 deltas and recomputed local scores of a private leaderboard
+    Leaderboard {
+        /// A leaderboard JSON file, its URL, or the leaderboard id to fetch
+        source: String,
+
+        /// Site to fetch leaderboard ids from, defaults to AOC_BASE_URL or https://adventofcode.com
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+
+    /// Write an HTML report of answers, timings and timing trends from the run history
+    Report {
+        /// The report file
+        #[arg(short, long, default_value = "report.html")]
+        out: PathBuf,
+
+        /// Report the last run in the history instead of running the days
+        #[arg(long, action)]
+        from_history: bool,
+
+        /// Link day sources as URL/day_XX.rs, defaults to the src directory next to the report
+        #[arg(long, default_value = report::SOURCE_URL)]
+        source_url: String,
+    },
+
+    /// Draw the puzzle state of one AOC day
+    Viz {
+        /// The AOC day to draw
+        #[arg(short, long)]
+        day: u8,
+
+        /// ansi prints to the terminal, svg, png and ppm write day_XX.svg / .png / .ppm
+        #[arg(short, long, default_value = "ansi")]
+        format: Format,
+
+        /// Pixels per grid cell for svg, png and ppm
+        #[arg(long, default_value_t = 4)]
+        scale: usize,
+
+        /// Output directory, defaults to AOC_VIZ_DIR or viz/
+        #[arg(short, long)]
+        out_dir: Option<PathBuf>,
+
+        /// Draw this input file instead of the day's puzzle input
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+    },
+}
+
+fn fail(msg: impl std::fmt::Display) -> ! {
+    eprintln!("error: {}", msg);
+    std::process::exit(1);
+}
+
+fn select_days(day: Option<u8>) -> Vec<&'static Day> {
+    match day {
+        Some(day) => match DAYS.iter().find(|d| d.day == day) {
+            Some(d) => vec![d],
+            None => fail(format!("no solver for day {}", day)),
+        },
+        None => DAYS.iter().collect(),
+    }
+}
+
+// a plugin that fails to load is reported and skipped
+fn load_plugins() -> Plugins {
+    let (plugins, failed) = Plugins::load_dir(&plugin_dir());
+    for p in plugins.days() {
+        println!("plugin {}: day {} [{}]", p.path.display(), p.day, p.name);
+    }
+    for (path, e) in failed {
+        eprintln!("unable to load plugin {}: {}", path.display(), e);
+    }
+    plugins
+}
+
+// number of recent runs a day's expected duration is averaged over
+const EXPECTED_WINDOW: usize = 5;
+
+/// Runs `days` in parallel, starting the ones the history says take longest first,
+/// and prints every day as it finishes. The results are returned in `days` order.
+fn run_streaming<F>(
+    days: &[&Day],
+    run: &F,
+    expected: &HashMap<u8, Duration>,
+    redacted: bool,
+) -> Vec<Result<(Solution, bool)>>
+where
+    F: Fn(&Day) -> Result<(Solution, bool)> + Sync,
+{
+    let day_numbers: Vec<u8> = days.iter().map(|d| d.day).collect();
+    let order = longest_first(&day_numbers, expected);
+
+    let (tx, rx) = mpsc::channel::<(usize, Result<(Solution, bool)>)>();
+    thread::scope(|t| {
+        let printer = t.spawn(move || {
+            let mut results: Vec<Option<Result<(Solution, bool)>>> =
+                (0..days.len()).map(|_| None).collect();
+            for (done, (i, result)) in rx.into_iter().enumerate() {
+                let day = days[i].day;
+                let expected = expected
+                    .get(&day)
+                    .map_or(String::new(), |e| format!(", expected {:?}", e));
+                match &result {
+                    Ok((solution, cached)) => println!(
+                        "[{:>2}/{}] day {:02} done in {:?}{}{}",
+                        done + 1,
+                        days.len(),
+                        day,
+                        solution.duration,
+                        if *cached { " (cached)" } else { &expected },
+                        match redacted {
+                            true => String::new(),
+                            false => format!(": {}, {}", solution.part_a, solution.part_b),
+                        }
+                    ),
+                    Err(e) => println!(
+                        "[{:>2}/{}] day {:02} failed: {}",
+                        done + 1,
+                        days.len(),
+                        day,
+                        e
+                    ),
+                }
+                results[i] = Some(result);
+            }
+            results
+        });
+        // FIFO spawns start in the order given, unlike par_iter's splitting
+        rayon::scope_fifo(|s| {
+            for &i in &order {
+                let tx = tx.clone();
+                s.spawn_fifo(move |_| {
+                    let _ = tx.send((i, run(days[i])));
+                });
+            }
+        });
+        drop(tx);
+        let results = printer.join().unwrap();
+        // every spawned day sends exactly one result
+        results.into_iter().map(Option::unwrap).collect()
+    })
+}
+
+fn run_cold_child(day: u8) {
+    let Some(d) = DAYS.iter().find(|d| d.day == day) else {
+        fail(format!("no solver for day {}", day));
+    };
+    match cold::measure(d) {
+        Ok(timing) => println!("{}", timing.to_line()),
+        Err(e) => fail(e),
+    }
+}
+
+/// Runs each day in a fresh process, then twice in this one, and compares the
+/// cold timings with the second, warm, in-process run.
+fn run_cold(days: Vec<&Day>) {
+    let exe = env::current_exe().unwrap_or_else(|e| fail(format!("no runner executable: {}", e)));
+    println!(
+        "{:<4} {:>12} {:>12} {:>12} {:>12} {:>7}",
+        "day", "process", "pool init", "cold solve", "warm solve", "ratio"
+    );
+    let mut errors: Vec<String> = Vec::new();
+    for d in days {
+        let cold = cold::run_child(&exe, d.day);
+        let warm = (d.solve)().and_then(|_| (d.solve)());
+        match (cold, warm) {
+            (Ok(cold), Ok(warm)) => println!(
+                "{:<4} {:>12.2?} {:>12.2?} {:>12.2?} {:>12.2?} {:>6.1}x",
+                d.day,
+                cold.process,
+                cold.pool_init,
+                cold.solve,
+                warm.duration,
+                cold.solve.as_secs_f64() / warm.duration.as_secs_f64().max(1e-9)
+            ),
+            (Err(e), _) => {
+                println!("{:<4} failed", d.day);
+                errors.push(e.to_string());
+            }
+            (_, Err(e)) => {
+                println!("{:<4} failed", d.day);
+                errors.push(e.to_string());
+            }
+        }
+    }
+    if !errors.is_empty() {
+        eprintln!("\n{} day(s) failed:", errors.len());
+        for e in errors {
+            eprintln!("  {}", e);
+        }
+        std::process::exit(1);
+    }
+}
+
+fn run_command(command: Command, args: &Args) {
+    match command {
+        Command::Encrypt { remove_plain } => run_encrypt(remove_plain),
+        Command::Decrypt { remove_encrypted } => run_decrypt(remove_encrypted),
+        Command::Examples { day } => run_examples(day),
+        Command::Verify { day, record, plain } => {
+            run_verify(select_days(day), args.par, record, plain, args.redact)
+        }
+        Command::Puzzle {
+            day,
+            markdown,
+            refresh,
+            base_url,
+            width,
+        } => run_puzzle(day, markdown, refresh, base_url, width),
+        Command::Extract {
+            day,
+            block,
+            name,
+            base_url,
+        } => run_extract(day, block, name, base_url),
+        Command::Report {
+            out,
+            from_history,
+            source_url,
+        } => {
+            let options = report::Options {
+                redact: args.redact,
+                source_url,
+            };
+            run_report(&out, from_history, args.par, &options)
+        }
+        Command::Leaderboard { source, base_url } => run_leaderboard(&source, base_url),
+        Command::Viz {
+            day,
+            format,
+            scale,
+            out_dir,
+            input,
+        } => run_viz(day, format, scale, out_dir, input),
+    }
+}
+
+fn run_encrypt(remove_plain: bool) {
+    let key = input::load_key()
+        .unwrap_or_else(|e| fail(format!("invalid input key: {}", e)))
+        .unwrap_or_else(|| {
+            let key = input::generate_key();
+            let path = input::key_file();
+            if let Err(e) = fs::write(&path, input::format_key(&key)) {
+                fail(format!("unable to write {}: {}", path.display(), e));
+            }
+            println!("generated new key in {}", path.display());
+            key
+        });
+    let dir = input::data_dir();
+    print_migrated(&dir, input::encrypt_dir(&dir, &key, remove_plain));
+}
+
+fn run_decrypt(remove_encrypted: bool) {
+    let key = input::load_key()
+        .unwrap_or_else(|e| fail(format!("invalid input key: {}", e)))
+        .unwrap_or_else(|| fail("no input key, set AOC_INPUT_KEY or create the key file"));
+    let dir = input::data_dir();
+    print_migrated(&dir, input::decrypt_dir(&dir, &key, remove_encrypted));
+}
+
+// the files an encrypt or decrypt wrote
+fn print_migrated(dir: &Path, written: io::Result<Vec<PathBuf>>) {
+    let written =
+        written.unwrap_or_else(|e| fail(format!("unable to migrate {}: {}", dir.display(), e)));
+    for path in written {
+        println!("wrote {}", path.display());
+    }
+}
+
+fn run_puzzle(day: u8, markdown: bool, refresh: bool, base_url: Option<String>, width: usize) {
+    let base_url = base_url.unwrap_or_else(puzzle::base_url);
+    let session = puzzle::session();
+    let html = puzzle::load_page(
+        &puzzle::puzzle_dir(),
+        &base_url,
+        day,
+        session.as_deref(),
+        refresh,
+    )
+    .unwrap_or_else(|e| fail(format!("unable to load the day {} puzzle: {}", day, e)));
+    let style = if markdown {
+        Style::Markdown
+    } else {
+        Style::Text
+    };
+    print!("{}", puzzle::render(&html, style, width));
+}
+
+fn run_leaderboard(source: &str, base_url: Option<String>) {
+    let base_url = base_url.unwrap_or_else(puzzle::base_url);
+    let session = puzzle::session();
+    let board = leaderboard::load(source, &base_url, session.as_deref())
+        .and_then(|json| Leaderboard::parse(&json))
+        .unwrap_or_else(|e| fail(format!("unable to load leaderboard {}: {}", source, e)));
+    print!("{}", board.report());
+}
+
+fn run_examples(day: Option<u8>) {
+    let examples = load_examples(&examples_dir())
+        .unwrap_or_else(|e| fail(format!("unable to load examples: {}", e)));
+    let checks: Vec<_> = examples
+        .iter()
+        .filter(|e| day.is_none_or(|d| d == e.day))
+        .flat_map(run_example)
+        .collect();
+    for check in &checks {
+        println!("{}", check);
+    }
+
+    let failed = checks.iter().filter(|c| !c.passed()).count();
+    println!("\n{} passed, {} failed", checks.len() - failed, failed);
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn run_verify(days: Vec<&Day>, par: bool, record: bool, plain: bool, redacted: bool) {
+    let path = answers_file();
+    let mut answers = Answers::load(&path)
+        .unwrap_or_else(|e| fail(format!("unable to load {}: {}", path.display(), e)));
+
+    let results: Vec<Result<Solution>> = if par {
+        days.par_iter().map(|d| (d.solve)()).collect()
+    } else {
+        days.iter().map(|d| (d.solve)()).collect()
+    };
+
+    let mut counts: HashMap<Status, usize> = HashMap::new();
+    let mut errors: Vec<AocError> = Vec::new();
+    for result in results {
+        match result {
+            Ok(solution) => {
+                for answer in [&solution.part_a, &solution.part_b] {
+                    let status = answers.check(answer);
+                    let shown = if redacted {
+                        redact(answer)
+                    } else {
+                        answer.clone()
+                    };
+                    println!("{:<30} {:<4} {:?}", shown, status, solution.duration);
+                    *counts.entry(status).or_default() += 1;
+                    // keep the salt of unchanged answers so the file only changes with them
+                    if record && status != Status::Pass {
+                        answers.record(answer, !plain);
+                    }
+                }
+            }
+            Err(e) => {
+                println!(
+                    "{:<30} {:<4}",
+                    format!("day_{:02} failed", e.day),
+                    Status::Fail
+                );
+                *counts.entry(Status::Fail).or_default() += 1;
+                errors.push(e);
+            }
+        }
+    }
+
+    let count = |status| counts.get(&status).copied().unwrap_or(0);
+    println!(
+        "\n{} ok, {} failed, {} without an expected answer",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Unknown)
+    );
+    for e in &errors {
+        eprintln!("  {}", e);
+    }
+    if record {
+        fs::write(&path, answers.to_string())
+            .unwrap_or_else(|e| fail(format!("unable to write {}: {}", path.display(), e)));
+        println!("wrote {}", path.display());
+    }
+    // recording keeps the new answers but still reports the mismatches
+    if count(Status::Fail) > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn run_report(out: &PathBuf, from_history: bool, par: bool, options: &report::Options) {
+    let path = history_file();
+    let mut runs = history::load(&path)
+        .unwrap_or_else(|e| fail(format!("unable to load {}: {}", path.display(), e)));
+    let rows = if from_history {
+        let Some(last) = runs.last() else {
+            fail(format!("no runs in {}", path.display()));
+        };
+        DayRow::from_history(last)
+    } else {
+        let days = select_days(None);
+        let results: Vec<Result<Solution>> = if par {
+            days.par_iter().map(|d| (d.solve)()).collect()
+        } else {
+            days.iter().map(|d| (d.solve)()).collect()
+        };
+        let mut run = Run::now();
+        run.parallel = par;
+        let mut rows = Vec::new();
+        for (d, result) in days.iter().zip(results) {
+            match result {
+                Ok(solution) => {
+                    run.days.push((d.day, solution.timings));
+                    rows.push(DayRow::from_solution(d.day, &solution));
+                }
+                Err(e) => rows.push(DayRow {
+                    day: d.day,
+                    error: Some(e.to_string()),
+                    ..DayRow::default()
+                }),
+            }
+        }
+        record_run(&run);
+        runs.push(run);
+        rows
+    };
+    fs::write(out, report::render(&rows, &runs, options))
+        .unwrap_or_else(|e| fail(format!("unable to write {}: {}", out.display(), e)));
+    println!("wrote {}", out.display());
+}
+
+// history is best effort, a run isn't failed for it
+fn record_run(run: &Run) {
+    if run.days.is_empty() {
+        return;
+    }
+    if let Err(e) = history::append(&history_file(), run) {
+        eprintln!("unable to record the run history: {}", e);
+    }
+}
+
+fn run_extract(day: u8, block: Option<usize>, name: Option<String>, base_url: Option<String>) {
+    let base_url = base_url.unwrap_or_else(puzzle::base_url);
+    let session = puzzle::session();
+    let html = puzzle::load_page(
+        &puzzle::puzzle_dir(),
+        &base_url,
+        day,
+        session.as_deref(),
+        false,
+    )
+    .unwrap_or_else(|e| fail(format!("unable to load the day {} puzzle: {}", day, e)));
+    let extracted = puzzle::extract_examples(&html);
+    if extracted.blocks.is_empty() {
+        fail(format!("no code blocks on the day {} page", day));
+    }
+
+    let dir = examples_dir();
+    let day_dir = dir.join(format!("day_{:02}", day));
+    let existing = load_day_examples(&day_dir, day).unwrap_or_default();
+    for (i, text) in extracted.blocks.iter().enumerate() {
+        let known = existing.iter().find(|e| e.input == *text);
+        println!("--- block {} ---", i + 1);
+        for line in text.lines().take(6) {
+            println!("  {}", line);
+        }
+        if text.lines().count() > 6 {
+            println!("  ... {} lines", text.lines().count());
+        }
+        if let Some(e) = known {
+            println!("  (already example {})", e.name);
+        }
+    }
+    let show = |answer: &Option<String>| answer.clone().unwrap_or_else(|| "-".to_string());
+    println!(
+        "\nanswers found: a: {}, b: {}",
+        show(&extracted.part_a),
+        show(&extracted.part_b)
+    );
+
+    let block = block.unwrap_or_else(|| {
+        print!(
+            "which block is the example? [1-{}] ",
+            extracted.blocks.len()
+        );
+        io::stdout().flush().unwrap_or_else(|e| fail(e));
+        let mut line = String::new();
+        io::stdin().read_line(&mut line).unwrap_or_else(|e| fail(e));
+        line.trim()
+            .parse()
+            .unwrap_or_else(|_| fail(format!("not a block number '{}'", line.trim())))
+    });
+    let Some(text) = block.checked_sub(1).and_then(|i| extracted.blocks.get(i)) else {
+        fail(format!("no block {}", block));
+    };
+
+    let name = name.unwrap_or_else(|| next_name(&day_dir));
+    let example = write_example(
+        &dir,
+        day,
+        &name,
+        text,
+        extracted.part_a.as_deref(),
+        extracted.part_b.as_deref(),
+    )
+    .unwrap_or_else(|e| fail(format!("unable to write example {}: {}", name, e)));
+    println!("wrote {}/{}.txt\n", day_dir.display(), name);
+    for check in run_example(&example) {
+        println!("{}", check);
+    }
+}
+
+fn run_viz(
+    day: u8,
+    format: Format,
+    scale: usize,
+    out_dir: Option<PathBuf>,
+    input: Option<PathBuf>,
+) {
+    let Some(d) = DAYS.iter().find(|d| d.day == day) else {
+        fail(format!("no solver for day {}", day));
+    };
+    let Some(visualize) = d.visualize else {
+        fail(format!("day {} has no visualization", day));
+    };
+    let text = match input {
+        Some(path) => fs::read_to_string(&path)
+            .unwrap_or_else(|e| fail(format!("unable to read {}: {}", path.display(), e))),
+        None => input::load_input(day).unwrap_or_else(|e| fail(e)),
+    };
+    let canvas = visualize(&text).unwrap_or_else(|e| fail(e));
+    if format == Format::Ansi {
+        // plain characters when piped
+        print!("{}", viz::to_ansi(&canvas, render::use_color()));
+        return;
+    }
+    let bytes = viz::render(&canvas, format, scale.max(1))
+        .unwrap_or_else(|e| fail(format!("unable to draw day {}: {}", day, e)));
+
+    let dir = out_dir.unwrap_or_else(viz::viz_dir);
+    let path = dir.join(format!("day_{:02}.{}", day, format.extension()));
+    fs::create_dir_all(&dir)
+        .and_then(|_| fs::write(&path, bytes))
+        .unwrap_or_else(|e| fail(format!("unable to write {}: {}", path.display(), e)));
+    println!("wrote {}", path.display());
 }
 
 fn main() {
     let args = Args::parse();
+    if let Some(day) = args.cold_child {
+        return run_cold_child(day);
+    }
     println!("{:?}", args);
 
-    let fs = vec![
-        solve_day_01,
-        solve_day_02,
-        solve_day_03,
-        solve_day_04,
-        solve_day_05,
-        solve_day_06,
-        solve_day_07,
-        solve_day_08,
-        solve_day_09,
-        solve_day_10,
-        solve_day_11,
-    ];
-
-    let days_m: HashMap<u8, fn() -> Solution> = fs
+    if let Some(command) = args.command.clone() {
+        run_command(command, &args);
+        return;
+    }
+
+    let days = select_days(args.day);
+    if args.cold {
+        return run_cold(days);
+    }
+    let plugins = load_plugins();
+    let plugin_days: Vec<&PluginDay> = plugins
+        .days()
         .iter()
-        .enumerate()
-        .map(|(i, f)| ((i + 1) as u8, *f))
+        .filter(|p| args.day.is_none_or(|d| d == p.day))
         .collect();
 
+    let mut cache = args.cached.then(|| {
+        let fingerprint = build_fingerprint()
+            .unwrap_or_else(|e| fail(format!("unable to fingerprint the build: {}", e)));
+        Cache::load(&cache_file(), &fingerprint)
+            .unwrap_or_else(|e| fail(format!("unable to load the answer cache: {}", e)))
+    });
+    // a day whose input can't be loaded isn't cached, its solver reports the error
+    let hashes: HashMap<u8, String> = match cache {
+        Some(_) => days
+            .iter()
+            .filter_map(|d| Some((d.day, input_hash(&input::load_input(d.day).ok()?))))
+            .collect(),
+        None => HashMap::new(),
+    };
+    let run = |d: &Day| -> Result<(Solution, bool)> {
+        let cached = cache.as_ref().zip(hashes.get(&d.day));
+        match cached.and_then(|(cache, hash)| cache.get(d.day, hash)) {
+            Some(solution) => Ok((solution, true)),
+            None => (d.solve)().map(|solution| (solution, false)),
+        }
+    };
+
+    let runs = history::load(&history_file()).unwrap_or_default();
+    let expected = expected_durations(&runs, EXPECTED_WINDOW);
+    let mut run_history = Run::now();
+    run_history.parallel = args.par;
     let start = Instant::now();
-    let mut solutions: Vec<Solution> = Vec::new();
-    if let Some(day) = args.day {
-        if let Some(f) = days_m.get(&day) {
-            let solution = f();
+    let results: Vec<Result<(Solution, bool)>> = if args.par {
+        run_streaming(&days, &run, &expected, args.redact)
+    } else {
+        days.iter().map(|&d| run(d)).collect()
+    };
+    let elapsed = start.elapsed();
+    println!();
+    let mut errors: Vec<AocError> = Vec::new();
+    for (d, result) in days.iter().zip(results) {
+        match result {
+            Ok((solution, cached)) => {
+                let show = |answer: &str| match args.redact {
+                    true => redact(answer),
+                    false => answer.to_string(),
+                };
+                println!(
+                    "{:<30}, {:<30}, {:?}{}",
+                    show(&solution.part_a),
+                    show(&solution.part_b),
+                    solution.duration,
+                    if cached { " (cached)" } else { "" }
+                );
+                if let (Some(cache), Some(hash), false) = (&mut cache, hashes.get(&d.day), cached) {
+                    cache.insert(d.day, hash, &solution);
+                }
+                if !cached {
+                    run_history.days.push((d.day, solution.timings));
+                }
+            }
+            Err(e) => {
+                println!("{:<30}, {:<30}", format!("day_{:02} failed", e.day), "-");
+                errors.push(e);
+            }
+        }
+    }
+    // alternative solvers from plugins run against the same inputs, after the built in ones
+    let run_plugin = |p: &PluginDay| -> Result<Solution> { p.solve(&input::load_input(p.day)?) };
+    let plugin_results: Vec<Result<Solution>> = if args.par {
+        plugin_days.par_iter().map(|&p| run_plugin(p)).collect()
+    } else {
+        plugin_days.iter().map(|&p| run_plugin(p)).collect()
+    };
+    for (p, result) in plugin_days.iter().zip(plugin_results) {
+        match result {
+            Ok(solution) => {
+                let show = |answer: &str| match args.redact {
+                    true => redact(answer),
+                    false => answer.to_string(),
+                };
+                println!(
+                    "{:<30}, {:<30}, {:?} [{}]",
+                    show(&solution.part_a),
+                    show(&solution.part_b),
+                    solution.duration,
+                    p.name
+                );
+            }
+            Err(e) => {
+                println!(
+                    "{:<30}, {:<30}",
+                    format!("day_{:02} failed [{}]", e.day, p.name),
+                    "-"
+                );
+                errors.push(e);
+            }
+        }
+    }
+    println!("\ntotal elapsed time: {:?}", start.elapsed());
+    if args.par {
+        // the times of this run are measured while the days compete for cores, so the
+        // baseline is the sequential runs in the history, this run's times where there are none
+        let sequential_runs: Vec<Run> = runs.into_iter().filter(|r| !r.parallel).collect();
+        let baseline = expected_durations(&sequential_runs, EXPECTED_WINDOW);
+        let sequential: Duration = run_history
+            .days
+            .iter()
+            .map(|(day, t)| baseline.get(day).copied().unwrap_or(t.total()))
+            .sum();
+        if !elapsed.is_zero() && !sequential.is_zero() {
             println!(
-                "{:<30}, {:<30}, {:?}",
-                solution.part_a, solution.part_b, solution.duration
+                "parallel speedup: {:.1}x, {:?} of sequential day times in {:?}",
+                sequential.as_secs_f64() / elapsed.as_secs_f64(),
+                sequential,
+                elapsed
             );
         }
-    } else if args.par && args.day.is_none() {
-        fs.par_iter().map(|&f| f()).collect_into_vec(&mut solutions);
-    } else {
-        solutions = fs.iter().map(|&f| f()).collect();
     }
-    println!();
-    for solution in solutions {
-        println!(
-            "{:<30}, {:<30}, {:?}",
-            solution.part_a, solution.part_b, solution.duration
-        );
+    record_run(&run_history);
+    if let Some(cache) = cache {
+        if let Err(e) = cache.save() {
+            eprintln!("unable to save the answer cache: {}", e);
+        }
+    }
+
+    if !errors.is_empty() {
+        eprintln!("\n{} day(s) failed:", errors.len());
+        for e in errors {
+            eprintln!("  {}", e);
+        }
+        std::process::exit(1);
     }
-    println!("\ntotal elapsed time: {:?}", start.elapsed());
 }
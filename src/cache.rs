@@ -0,0 +1,189 @@
+//! Answer cache for the `--cached` runner flag.
+//!
+//! Each answer is keyed by day, part, the SHA-256 of the input and a build
+//! fingerprint, so a changed input or a rebuilt solver never serves a stale answer.
+//! The cache is a tab separated file, `.aoc_cache` in the crate root unless
+//! `AOC_CACHE_FILE` says otherwise. Entries from other builds are dropped on save.
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use sha2::{Digest, Sha256};
+
+use crate::api::Part;
+use crate::common::{hex, Solution, Timings};
+
+pub fn cache_file() -> PathBuf {
+    env::var_os("AOC_CACHE_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/.aoc_cache")))
+}
+
+pub fn input_hash(input: &str) -> String {
+    hex(&Sha256::digest(input.as_bytes()))
+}
+
+/// Hash of the running executable, which changes whenever any solver is rebuilt.
+pub fn build_fingerprint() -> io::Result<String> {
+    let exe = fs::read(env::current_exe()?)?;
+    Ok(hex(&Sha256::digest(&exe)))
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Key {
+    day: u8,
+    part: Part,
+    input_hash: String,
+}
+
+#[derive(Clone, Debug)]
+struct Entry {
+    answer: String,
+    duration: Duration,
+}
+
+pub struct Cache {
+    path: PathBuf,
+    fingerprint: String,
+    entries: HashMap<Key, Entry>,
+}
+
+impl Cache {
+    /// Loads the entries of `path` made by the build `fingerprint`, a missing file is empty.
+    pub fn load(path: &Path, fingerprint: &str) -> io::Result<Cache> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        let entries = text
+            .lines()
+            .filter_map(|line| parse_line(line, fingerprint))
+            .collect();
+        Ok(Cache {
+            path: path.to_path_buf(),
+            fingerprint: fingerprint.to_string(),
+            entries,
+        })
+    }
+
+    /// The cached solution of `day` when both parts are cached for this input.
+    pub fn get(&self, day: u8, input_hash: &str) -> Option<Solution> {
+        let entry = |part| {
+            self.entries.get(&Key {
+                day,
+                part,
+                input_hash: input_hash.to_string(),
+            })
+        };
+        let (a, b) = (entry(Part::A)?, entry(Part::B)?);
+        Some(Solution {
+            part_a: a.answer.clone(),
+            part_b: b.answer.clone(),
+            duration: a.duration,
+            timings: Timings::default(),
+        })
+    }
+
+    pub fn insert(&mut self, day: u8, input_hash: &str, solution: &Solution) {
+        for (part, answer) in [(Part::A, &solution.part_a), (Part::B, &solution.part_b)] {
+            let key = Key {
+                day,
+                part,
+                input_hash: input_hash.to_string(),
+            };
+            let entry = Entry {
+                answer: answer.clone(),
+                duration: solution.duration,
+            };
+            self.entries.insert(key, entry);
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let mut lines: Vec<String> = self
+            .entries
+            .iter()
+            .map(|(k, e)| {
+                format!(
+                    "{}\t{}\t{}\t{}\t{}\t{}\n",
+                    k.day,
+                    k.part,
+                    k.input_hash,
+                    self.fingerprint,
+                    e.duration.as_nanos(),
+                    e.answer
+                )
+            })
+            .collect();
+        lines.sort();
+        fs::write(&self.path, lines.concat())
+    }
+}
+
+// day, part, input hash, fingerprint, duration in ns, answer
+fn parse_line(line: &str, fingerprint: &str) -> Option<(Key, Entry)> {
+    let fields: Vec<&str> = line.splitn(6, '\t').collect();
+    let [day, part, input_hash, build, nanos, answer] = fields[..] else {
+        return None;
+    };
+    if build != fingerprint {
+        return None;
+    }
+    let key = Key {
+        day: day.parse().ok()?,
+        part: part.parse().ok()?,
+        input_hash: input_hash.to_string(),
+    };
+    let entry = Entry {
+        answer: answer.to_string(),
+        duration: Duration::from_nanos(nanos.parse().ok()?),
+    };
+    Some((key, entry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solution() -> Solution {
+        Solution {
+            part_a: "day_01_a = 11".to_string(),
+            part_b: "day_01_b = 31".to_string(),
+            duration: Duration::from_micros(42),
+            timings: Timings::default(),
+        }
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let path = env::temp_dir().join(format!("aoc_cache_test_{}", std::process::id()));
+        let hash = input_hash("3   4\n");
+
+        let mut cache = Cache::load(&path, "build-1").unwrap();
+        assert!(cache.get(1, &hash).is_none());
+        cache.insert(1, &hash, &solution());
+        cache.save().unwrap();
+
+        let cached = Cache::load(&path, "build-1")
+            .unwrap()
+            .get(1, &hash)
+            .unwrap();
+        assert_eq!(
+            ("day_01_a = 11", "day_01_b = 31", Duration::from_micros(42)),
+            (&cached.part_a[..], &cached.part_b[..], cached.duration)
+        );
+        // another input or another build misses
+        let cache = Cache::load(&path, "build-1").unwrap();
+        assert!(cache.get(1, &input_hash("3   5\n")).is_none());
+        assert!(cache.get(2, &hash).is_none());
+        assert!(Cache::load(&path, "build-2")
+            .unwrap()
+            .get(1, &hash)
+            .is_none());
+        fs::remove_file(&path).unwrap();
+    }
+}
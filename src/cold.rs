@@ -0,0 +1,112 @@
+//! Cold start timings for `aoc2024 --cold`.
+//!
+//! Days solved one after the other in a process get warmed up by the days before
+//! them: the allocator has grown, rayon's pool is running and inputs are in the
+//! page cache. In cold mode the runner starts itself again per day, the child
+//! initializes rayon's pool, solves the day and prints one line of timings, and the
+//! parent adds the wall time of the whole process.
+use std::io;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use crate::days::Day;
+use crate::error::Result;
+
+/// Hidden runner flag that makes the process a cold start child for one day.
+pub const CHILD_FLAG: &str = "--cold-child";
+
+// starts the child's line of timings in its output
+const MARKER: &str = "aoc2024-cold";
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ColdTiming {
+    pub day: u8,
+    /// From spawning the child until it exited.
+    pub process: Duration,
+    /// Starting rayon's global pool.
+    pub pool_init: Duration,
+    /// The day's solve, like the duration of a warm run.
+    pub solve: Duration,
+}
+
+impl ColdTiming {
+    pub fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}",
+            MARKER,
+            self.day,
+            self.pool_init.as_nanos(),
+            self.solve.as_nanos()
+        )
+    }
+
+    /// The timings in a child's output, without `process`.
+    pub fn parse(output: &str) -> Option<ColdTiming> {
+        output.lines().find_map(|line| {
+            let fields: Vec<&str> = line.trim().split('\t').collect();
+            let [MARKER, day, pool_init, solve] = fields[..] else {
+                return None;
+            };
+            Some(ColdTiming {
+                day: day.parse().ok()?,
+                process: Duration::ZERO,
+                pool_init: Duration::from_nanos(pool_init.parse().ok()?),
+                solve: Duration::from_nanos(solve.parse().ok()?),
+            })
+        })
+    }
+}
+
+/// Runs in the child: starts rayon's pool, then solves `day`.
+pub fn measure(day: &Day) -> Result<ColdTiming> {
+    let start = Instant::now();
+    // every worker thread runs once, so the pool is completely up
+    rayon::broadcast(|_| ());
+    let pool_init = start.elapsed();
+    let solution = (day.solve)()?;
+    Ok(ColdTiming {
+        day: day.day,
+        process: Duration::ZERO,
+        pool_init,
+        solve: solution.duration,
+    })
+}
+
+/// Runs `exe` as a cold start child for `day` and returns its timings.
+pub fn run_child(exe: &Path, day: u8) -> io::Result<ColdTiming> {
+    let start = Instant::now();
+    let output = Command::new(exe)
+        .arg(CHILD_FLAG)
+        .arg(day.to_string())
+        .output()?;
+    let process = start.elapsed();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match ColdTiming::parse(&stdout) {
+        Some(timing) if output.status.success() => Ok(ColdTiming { process, ..timing }),
+        _ => Err(io::Error::other(format!(
+            "day {} child failed ({}): {}",
+            day,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_test() {
+        let timing = ColdTiming {
+            day: 6,
+            process: Duration::ZERO,
+            pool_init: Duration::from_micros(250),
+            solve: Duration::from_millis(40),
+        };
+        let output = format!("Args {{ .. }}\n{}\n", timing.to_line());
+        assert_eq!(Some(timing), ColdTiming::parse(&output));
+        assert_eq!(None, ColdTiming::parse("aoc2024-cold\t6\tx\t1\n"));
+    }
+}
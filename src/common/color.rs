@@ -0,0 +1,51 @@
+//! Colours shared by the terminal renderer and the image encoders.
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// `#rrggbb`, for SVG and HTML.
+    pub fn hex(self) -> String {
+        let Rgb(r, g, b) = self;
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+pub const BACKGROUND: Rgb = Rgb(16, 16, 24);
+pub const DIM: Rgb = Rgb(64, 64, 80);
+pub const WALL: Rgb = Rgb(150, 150, 160);
+pub const PATH: Rgb = Rgb(240, 200, 60);
+pub const START: Rgb = Rgb(80, 220, 100);
+pub const MARK: Rgb = Rgb(235, 60, 60);
+pub const MARK_DIM: Rgb = Rgb(150, 50, 120);
+
+/// A distinct colour for id `i`, hues step by the golden angle.
+pub fn palette(i: usize) -> Rgb {
+    let hue = (i as f64 * 137.508) % 360.0;
+    let (s, v) = (0.65, 0.95);
+    let c = v * s;
+    let x = c * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let (r, g, b) = match (hue / 60.0) as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = v - c;
+    let to_u8 = |f: f64| ((f + m) * 255.0).round() as u8;
+    Rgb(to_u8(r), to_u8(g), to_u8(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn palette_test() {
+        assert_ne!(palette(0), palette(1));
+        assert_eq!(palette(3), palette(3));
+        assert_eq!("#9696a0", WALL.hex());
+    }
+}
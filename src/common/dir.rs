@@ -0,0 +1,97 @@
+use crate::common::Vec2;
+
+/// A compass direction on a grid, north is up (row - 1).
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Dir {
+    N,
+    Ne,
+    E,
+    Se,
+    S,
+    Sw,
+    W,
+    Nw,
+}
+
+impl Dir {
+    /// The four orthogonal directions, clockwise from north.
+    pub const ORTHOGONAL: [Dir; 4] = [Dir::N, Dir::E, Dir::S, Dir::W];
+
+    /// The four diagonal directions, clockwise from north east.
+    pub const DIAGONAL: [Dir; 4] = [Dir::Ne, Dir::Se, Dir::Sw, Dir::Nw];
+
+    /// All eight directions, clockwise from north.
+    pub const ALL: [Dir; 8] = [
+        Dir::N,
+        Dir::Ne,
+        Dir::E,
+        Dir::Se,
+        Dir::S,
+        Dir::Sw,
+        Dir::W,
+        Dir::Nw,
+    ];
+
+    /// The (row, col) step one cell in this direction.
+    pub const fn offset(self) -> Vec2 {
+        match self {
+            Dir::N => Vec2::new(-1, 0),
+            Dir::Ne => Vec2::new(-1, 1),
+            Dir::E => Vec2::new(0, 1),
+            Dir::Se => Vec2::new(1, 1),
+            Dir::S => Vec2::new(1, 0),
+            Dir::Sw => Vec2::new(1, -1),
+            Dir::W => Vec2::new(0, -1),
+            Dir::Nw => Vec2::new(-1, -1),
+        }
+    }
+
+    /// Turned 90 degrees clockwise.
+    pub const fn turn_right(self) -> Dir {
+        self.rotate(2)
+    }
+
+    /// Turned 90 degrees counter clockwise.
+    pub const fn turn_left(self) -> Dir {
+        self.rotate(6)
+    }
+
+    pub const fn reverse(self) -> Dir {
+        self.rotate(4)
+    }
+
+    pub const fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    // eighth turns clockwise
+    const fn rotate(self, eighths: usize) -> Dir {
+        Dir::ALL[(self as usize + eighths) % 8]
+    }
+}
+
+impl From<Dir> for Vec2 {
+    fn from(d: Dir) -> Self {
+        d.offset()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turn_test() {
+        assert_eq!(Dir::E, Dir::N.turn_right());
+        assert_eq!(Dir::W, Dir::N.turn_left());
+        assert_eq!(Dir::Nw, Dir::Sw.turn_right());
+        assert_eq!(Dir::Se, Dir::Nw.reverse());
+        for d in Dir::ALL {
+            assert_eq!(d, d.turn_right().turn_left());
+            assert_eq!(-d.offset(), d.reverse().offset());
+            assert_eq!(d.is_diagonal(), d.offset().manhattan() == 2);
+        }
+        assert!(Dir::ORTHOGONAL.iter().all(|d| !d.is_diagonal()));
+        assert!(Dir::DIAGONAL.iter().all(|d| d.is_diagonal()));
+    }
+}
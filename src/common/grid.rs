@@ -0,0 +1,414 @@
+use std::fmt;
+use std::iter;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::common::{grid_size, Dir, Point, Vec2};
+use crate::error::ParseError;
+
+/// A rectangular grid stored row by row in one `Vec`.
+///
+/// `get` and `step` return `None` outside the grid, so puzzles don't need a
+/// border of padding cells. Indexing with a [`Point`] panics outside the grid.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    pub rows: usize,
+    pub cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            rows,
+            cols,
+            cells: vec![fill; rows * cols],
+        }
+    }
+
+    /// A grid with `f` of each point as its cell.
+    pub fn from_fn(rows: usize, cols: usize, f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..rows)
+            .flat_map(|r| (0..cols).map(move |c| Point::new(r, c)))
+            .map(f)
+            .collect();
+        Grid { rows, cols, cells }
+    }
+
+    /// A grid of `cells` in row major order, `None` unless there are `rows * cols` of them.
+    pub fn from_vec(rows: usize, cols: usize, cells: Vec<T>) -> Option<Self> {
+        (cells.len() == rows * cols).then_some(Grid { rows, cols, cells })
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.row < self.rows && p.col < self.cols
+    }
+
+    /// The point at signed position `v`, `None` when that is outside the grid.
+    pub fn point(&self, v: Vec2) -> Option<Point> {
+        Point::try_from(v).ok().filter(|&p| self.contains(p))
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.contains(p)
+            .then(|| &self.cells[p.row * self.cols + p.col])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        let cols = self.cols;
+        self.contains(p)
+            .then(|| &mut self.cells[p.row * cols + p.col])
+    }
+
+    /// The point `v` away from `p`, `None` when that is outside the grid.
+    pub fn step(&self, p: Point, v: impl Into<Vec2>) -> Option<Point> {
+        p.checked_add(v.into()).filter(|&p| self.contains(p))
+    }
+
+    /// The neighbours of `p` in `dirs` that are inside the grid, with their direction.
+    pub fn neighbors<'a>(
+        &'a self,
+        p: Point,
+        dirs: &'a [Dir],
+    ) -> impl Iterator<Item = (Dir, Point)> + 'a {
+        dirs.iter()
+            .filter_map(move |&d| self.step(p, d).map(|q| (d, q)))
+    }
+
+    /// The up to four orthogonal neighbours of `p`.
+    pub fn neighbors_4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(p, &Dir::ORTHOGONAL).map(|(_, q)| q)
+    }
+
+    /// The up to eight neighbours of `p`, diagonals included.
+    pub fn neighbors_8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(p, &Dir::ALL).map(|(_, q)| q)
+    }
+
+    /// `p` and every point after it in direction `d`, up to the edge.
+    pub fn ray(&self, p: Point, d: Dir) -> impl Iterator<Item = Point> + '_ {
+        iter::successors(self.get(p).map(|_| p), move |&q| self.step(q, d))
+    }
+
+    /// Every line through the grid in direction `d`, edge to edge: rows for
+    /// [`Dir::E`], columns for [`Dir::S`], diagonals for [`Dir::Se`] and
+    /// anti-diagonals for [`Dir::Sw`], reversed for the opposite directions.
+    /// Ordered by their first point, row by row.
+    pub fn lines(&self, d: Dir) -> impl Iterator<Item = Vec<Point>> + '_ {
+        self.positions()
+            .filter(move |&p| self.step(p, d.reverse()).is_none())
+            .map(move |p| self.ray(p, d).collect())
+    }
+
+    /// The cells of row `row`, left to right. Panics outside the grid.
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(
+            row < self.rows,
+            "row {} outside a {}x{} grid",
+            row,
+            self.rows,
+            self.cols
+        );
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// The cells of column `col`, top to bottom. Panics outside the grid.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(
+            col < self.cols,
+            "column {} outside a {}x{} grid",
+            col,
+            self.rows,
+            self.cols
+        );
+        self.cells[col..].iter().step_by(self.cols)
+    }
+
+    /// Every row, none for a grid without columns.
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        // chunks panics on a chunk size of 0
+        self.cells.chunks(self.cols.max(1))
+    }
+
+    pub fn iter_columns(&self) -> impl Iterator<Item = Vec<&T>> + '_ {
+        self.line_cells(Dir::S)
+    }
+
+    /// Lines down and to the right.
+    pub fn iter_diagonals(&self) -> impl Iterator<Item = Vec<&T>> + '_ {
+        self.line_cells(Dir::Se)
+    }
+
+    /// Lines down and to the left.
+    pub fn iter_anti_diagonals(&self) -> impl Iterator<Item = Vec<&T>> + '_ {
+        self.line_cells(Dir::Sw)
+    }
+
+    fn line_cells(&self, d: Dir) -> impl Iterator<Item = Vec<&T>> + '_ {
+        self.lines(d)
+            .map(|line| line.into_iter().map(|p| &self[p]).collect())
+    }
+
+    /// Every point, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |r| (0..cols).map(move |c| Point::new(r, c)))
+    }
+
+    /// Every point with its cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// The first point holding `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|(_, v)| *v == value).map(|(p, _)| p)
+    }
+
+    pub fn find_all(&self, value: &T) -> Vec<Point>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(|(_, v)| *v == value)
+            .map(|(p, _)| p)
+            .collect()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Rows become columns, mirrored along the main diagonal.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.cols, self.rows, |p| {
+            self[Point::new(p.col, p.row)].clone()
+        })
+    }
+
+    /// Turned a quarter clockwise.
+    pub fn rotate_90(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.cols, self.rows, |p| {
+            self[Point::new(self.rows - 1 - p.col, p.row)].clone()
+        })
+    }
+
+    pub fn rotate_180(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.rows, self.cols, |p| {
+            self[Point::new(self.rows - 1 - p.row, self.cols - 1 - p.col)].clone()
+        })
+    }
+
+    /// Turned a quarter counter clockwise.
+    pub fn rotate_270(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.cols, self.rows, |p| {
+            self[Point::new(p.col, self.cols - 1 - p.row)].clone()
+        })
+    }
+
+    /// Mirrored left to right.
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.rows, self.cols, |p| {
+            self[Point::new(p.row, self.cols - 1 - p.col)].clone()
+        })
+    }
+
+    /// Mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.rows, self.cols, |p| {
+            self[Point::new(self.rows - 1 - p.row, p.col)].clone()
+        })
+    }
+}
+
+impl Grid<char> {
+    /// The [`lines`](Grid::lines) in direction `d` as strings.
+    pub fn line_strings(&self, d: Dir) -> impl Iterator<Item = String> + '_ {
+        self.lines(d)
+            .map(|line| line.into_iter().map(|p| self[p]).collect())
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        match self.get(p) {
+            Some(v) => v,
+            None => panic!("{:?} outside a {}x{} grid", p, self.rows, self.cols),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (rows, cols) = (self.rows, self.cols);
+        match self.get_mut(p) {
+            Some(v) => v,
+            None => panic!("{:?} outside a {}x{} grid", p, rows, cols),
+        }
+    }
+}
+
+/// A character grid, one line per row, every line as long as the first.
+impl FromStr for Grid<char> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rows, cols) = grid_size(s)?;
+        let cells = s.lines().flat_map(|line| line.chars()).collect();
+        Ok(Grid { rows, cols, cells })
+    }
+}
+
+/// One line per row, the way it was parsed.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.iter_rows() {
+            for v in row {
+                write!(f, "{}", v)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const S: &str = "ab#\n#cd\n";
+
+    #[test]
+    fn parse_test() {
+        let grid: Grid<char> = S.parse().unwrap();
+        assert_eq!((2, 3), (grid.rows, grid.cols));
+        assert_eq!(Some(&'d'), grid.get(Point::new(1, 2)));
+        assert_eq!(None, grid.get(Point::new(2, 0)));
+        assert_eq!(S, grid.to_string());
+        assert_eq!(2, "ab\nc\n".parse::<Grid<char>>().unwrap_err().line);
+    }
+
+    #[test]
+    fn access_test() {
+        let mut grid: Grid<char> = S.parse().unwrap();
+        let p = |r, c| Point::new(r, c);
+        assert_eq!(Some(p(0, 2)), grid.find(&'#'));
+        assert_eq!(vec![p(0, 2), p(1, 0)], grid.find_all(&'#'));
+        assert_eq!(['#', 'c', 'd'], grid.row(1));
+        assert_eq!(vec![&'#', &'d'], grid.column(2).collect::<Vec<_>>());
+        assert_eq!(Some(p(0, 1)), grid.step(p(1, 2), Vec2::new(-1, -1)));
+        assert_eq!(None, grid.step(p(0, 0), Vec2::new(-1, 0)));
+        assert_eq!(None, grid.step(p(1, 2), Vec2::new(0, 1)));
+        assert_eq!(Some(p(1, 1)), grid.point(Vec2::new(1, 1)));
+        assert_eq!(None, grid.point(Vec2::new(1, -1)));
+        assert_eq!(None, grid.point(Vec2::new(2, 1)));
+        assert_eq!(Some(p(1, 1)), grid.step(p(0, 0), Dir::Se));
+
+        // corners and edges only have the neighbours inside the grid
+        assert_eq!(
+            vec![p(0, 1), p(1, 0)],
+            grid.neighbors_4(p(0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(3, grid.neighbors_8(p(0, 0)).count());
+        assert_eq!(5, grid.neighbors_8(p(1, 1)).count());
+        assert_eq!(
+            vec![(Dir::N, p(0, 2)), (Dir::W, p(1, 1))],
+            grid.neighbors(p(1, 2), &Dir::ORTHOGONAL)
+                .collect::<Vec<_>>()
+        );
+
+        grid[p(0, 0)] = '.';
+        let walls = grid.map(|&c| c == '#');
+        assert_eq!(2, walls.cells().iter().filter(|&&w| w).count());
+        assert_eq!(".b#\n#cd\n", grid.to_string());
+        assert_eq!(None, Grid::from_vec(2, 2, vec![0; 3]));
+    }
+
+    #[test]
+    fn empty_test() {
+        assert_eq!("", Grid::new(0, 0, '.').to_string());
+        assert_eq!("", Grid::new(3, 0, '.').to_string());
+        assert_eq!(0, Grid::from_fn(0, 2, |_| 0).iter_rows().count());
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 outside a 2x3 grid")]
+    fn column_bounds_test() {
+        let grid: Grid<char> = S.parse().unwrap();
+        let _ = grid.column(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 outside a 2x3 grid")]
+    fn row_bounds_test() {
+        let grid: Grid<char> = S.parse().unwrap();
+        let _ = grid.row(2);
+    }
+
+    #[test]
+    fn transform_test() {
+        let grid: Grid<char> = S.parse().unwrap();
+        assert_eq!("a#\nbc\n#d\n", grid.transpose().to_string());
+        assert_eq!("#a\ncb\nd#\n", grid.rotate_90().to_string());
+        assert_eq!("dc#\n#ba\n", grid.rotate_180().to_string());
+        assert_eq!("#d\nbc\na#\n", grid.rotate_270().to_string());
+        assert_eq!("#ba\ndc#\n", grid.flip_horizontal().to_string());
+        assert_eq!("#cd\nab#\n", grid.flip_vertical().to_string());
+        assert_eq!(grid, grid.rotate_90().rotate_270());
+        assert_eq!(grid.rotate_90(), grid.transpose().flip_horizontal());
+    }
+
+    #[test]
+    fn lines_test() {
+        let grid: Grid<char> = S.parse().unwrap();
+        let lines = |d| grid.line_strings(d).collect::<Vec<_>>();
+        assert_eq!(vec!["ab#", "#cd"], lines(Dir::E));
+        assert_eq!(vec!["#ba", "dc#"], lines(Dir::W));
+        assert_eq!(vec!["a#", "bc", "#d"], lines(Dir::S));
+        assert_eq!(vec!["ac", "bd", "#", "#"], lines(Dir::Se));
+        assert_eq!(vec!["a", "b#", "#c", "d"], lines(Dir::Sw));
+        assert_eq!(vec!["#", "#", "ca", "db"], lines(Dir::Nw));
+
+        assert_eq!(
+            vec![vec![&'a', &'#']],
+            grid.iter_columns().take(1).collect::<Vec<_>>()
+        );
+        assert_eq!(4, grid.iter_diagonals().count());
+        assert_eq!(vec![&'b', &'#'], grid.iter_anti_diagonals().nth(1).unwrap());
+        assert_eq!(0, grid.ray(Point::new(2, 0), Dir::E).count());
+    }
+}
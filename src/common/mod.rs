@@ -0,0 +1,115 @@
+use std::fmt::Debug;
+use std::iter::Iterator;
+use std::time::Duration;
+
+use crate::error::{ParseError, ParseResult};
+
+pub mod color;
+mod dir;
+mod grid;
+mod point;
+pub mod region;
+pub mod render;
+pub mod search;
+
+pub use dir::Dir;
+pub use grid::Grid;
+pub use point::{Point, Vec2};
+
+pub struct Solution {
+    pub part_a: String,
+    pub part_b: String,
+    pub duration: Duration,
+    pub timings: Timings,
+}
+
+/// Time spent parsing and in each part, adding up to the solution's duration.
+/// All zero when unknown, e.g. for answers served from the cache.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part_a: Duration,
+    pub part_b: Duration,
+}
+
+impl Timings {
+    /// From the elapsed times once parsed, once part A is solved and once done.
+    pub fn from_marks(parsed: Duration, solved_a: Duration, done: Duration) -> Self {
+        Timings {
+            parse: parsed,
+            part_a: solved_a.saturating_sub(parsed),
+            part_b: done.saturating_sub(solved_a),
+        }
+    }
+
+    pub fn total(&self) -> Duration {
+        self.parse + self.part_a + self.part_b
+    }
+}
+
+/// Two lowercase hex digits per byte, for hashes and keys.
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Checks that `s` is a non empty rectangular grid, returning (rows, cols).
+pub fn grid_size(s: &str) -> ParseResult<(usize, usize)> {
+    let mut cols = None;
+    let mut rows = 0;
+    for (row, line) in s.lines().enumerate() {
+        let n = line.chars().count();
+        match cols {
+            None => cols = Some(n),
+            Some(c) if c != n => {
+                let msg = format!("expected {} columns, found {}", c, n);
+                return Err(ParseError::new(row + 1, n.min(c) + 1, msg));
+            }
+            _ => (),
+        }
+        rows += 1;
+    }
+    match cols {
+        Some(c) if c > 0 => Ok((rows, c)),
+        _ => Err(ParseError::new(1, 1, "empty grid")),
+    }
+}
+
+/// Returns k nested 'loops' from xs, ordered.
+///
+/// `k_nested_recur`(3, &[1,2]) sudo code same as:
+///
+///  for (i,vi) 0..2 { for (j,vj) in i..2 { for (k,vk) in j..2 { list.push(vi, vj, vk) } } }
+///
+/// Recursive solution.
+///
+/// ```
+/// use lib::common::k_nested_recur;
+///
+/// let res = [[1,1,1], [1,1,2], [1,2,1], [1,2,2], [2,1,1], [2,1,2], [2,2,1], [2,2,2]];
+/// assert_eq!(k_nested_recur(3, &[1,2]), res);
+///
+/// let xss = k_nested_recur(8, &["red", "green", "blue", "orange"]);
+/// assert_eq!(xss.len(), 65536);
+///
+/// ```
+pub fn k_nested_recur<T>(k: usize, xs: &[T]) -> Vec<Vec<T>>
+where
+    T: Clone + PartialEq + Ord + Debug,
+{
+    match k {
+        0 => vec![vec![]],
+        1 => xs.iter().map(|x| vec![x.clone()]).collect(),
+        _ => {
+            let mut list: Vec<Vec<T>> = Vec::new();
+            for x in xs {
+                let ts = k_nested_recur(k - 1, xs);
+                for mut t in ts {
+                    t.push(x.clone());
+                    list.push(t);
+                }
+            }
+            list.sort();
+            list
+        }
+    }
+}
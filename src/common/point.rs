@@ -0,0 +1,185 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A cell of a grid, row 0 is the top.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+}
+
+/// A signed step or position, which may lie outside any grid.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Vec2 {
+    pub row: isize,
+    pub col: isize,
+}
+
+impl Point {
+    pub const fn new(row: usize, col: usize) -> Self {
+        Point { row, col }
+    }
+
+    /// `self + v`, `None` when that is above or left of the origin.
+    pub fn checked_add(self, v: Vec2) -> Option<Point> {
+        Some(Point {
+            row: self.row.checked_add_signed(v.row)?,
+            col: self.col.checked_add_signed(v.col)?,
+        })
+    }
+
+    pub fn manhattan(self, other: Point) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+
+    pub fn chebyshev(self, other: Point) -> usize {
+        self.row
+            .abs_diff(other.row)
+            .max(self.col.abs_diff(other.col))
+    }
+}
+
+impl Vec2 {
+    pub const fn new(row: isize, col: isize) -> Self {
+        Vec2 { row, col }
+    }
+
+    /// Length in steps along rows and columns.
+    pub fn manhattan(self) -> usize {
+        self.row.unsigned_abs() + self.col.unsigned_abs()
+    }
+
+    /// Length in king moves, diagonal steps included.
+    pub fn chebyshev(self) -> usize {
+        self.row.unsigned_abs().max(self.col.unsigned_abs())
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, p: Point) -> Point {
+        Point::new(self.row + p.row, self.col + p.col)
+    }
+}
+
+/// The step from `p` to `self`.
+impl Sub for Point {
+    type Output = Vec2;
+
+    fn sub(self, p: Point) -> Vec2 {
+        Vec2::from(self) - Vec2::from(p)
+    }
+}
+
+impl Mul<usize> for Point {
+    type Output = Point;
+
+    fn mul(self, k: usize) -> Point {
+        Point::new(self.row * k, self.col * k)
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, v: Vec2) -> Vec2 {
+        Vec2::new(self.row + v.row, self.col + v.col)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, v: Vec2) {
+        *self = *self + v;
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, v: Vec2) -> Vec2 {
+        Vec2::new(self.row - v.row, self.col - v.col)
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, v: Vec2) {
+        *self = *self - v;
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.row, -self.col)
+    }
+}
+
+impl Mul<isize> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, k: isize) -> Vec2 {
+        Vec2::new(self.row * k, self.col * k)
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((row, col): (usize, usize)) -> Self {
+        Point::new(row, col)
+    }
+}
+
+impl From<Point> for (usize, usize) {
+    fn from(p: Point) -> Self {
+        (p.row, p.col)
+    }
+}
+
+impl From<(isize, isize)> for Vec2 {
+    fn from((row, col): (isize, isize)) -> Self {
+        Vec2::new(row, col)
+    }
+}
+
+impl From<Point> for Vec2 {
+    fn from(p: Point) -> Self {
+        Vec2::new(p.row as isize, p.col as isize)
+    }
+}
+
+/// Fails for positions above or left of the origin.
+impl TryFrom<Vec2> for Point {
+    type Error = Vec2;
+
+    fn try_from(v: Vec2) -> Result<Self, Self::Error> {
+        Point::default().checked_add(v).ok_or(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_test() {
+        let (a, b) = (Point::new(1, 5), Point::new(4, 1));
+        assert_eq!(Vec2::new(3, -4), b - a);
+        assert_eq!(Some(b), a.checked_add(b - a));
+        assert_eq!(None, a.checked_add(Vec2::new(-2, 0)));
+        assert_eq!(Point::new(5, 6), a + b);
+        assert_eq!(Point::new(2, 10), a * 2);
+        assert_eq!(Vec2::new(-6, 8), (b - a) * -2);
+        assert_eq!(Vec2::new(-3, 4), -(b - a));
+
+        assert_eq!((7, 4), (a.manhattan(b), a.chebyshev(b)));
+        assert_eq!((7, 4), ((b - a).manhattan(), (b - a).chebyshev()));
+    }
+
+    #[test]
+    fn conversion_test() {
+        assert_eq!(Point::new(2, 3), (2, 3).into());
+        assert_eq!((2, 3), Point::new(2, 3).into());
+        assert_eq!(Ok(Point::new(2, 3)), Point::try_from(Vec2::new(2, 3)));
+        assert_eq!(Err(Vec2::new(-1, 3)), Point::try_from(Vec2::new(-1, 3)));
+    }
+}
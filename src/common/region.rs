@@ -0,0 +1,218 @@
+//! Connected regions of grid cells and their geometry.
+use crate::common::{Dir, Grid, Point};
+
+/// Which neighbours join cells into one region.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Connectivity {
+    /// Orthogonal neighbours only.
+    Four,
+    /// Diagonal neighbours too.
+    Eight,
+}
+
+impl Connectivity {
+    pub fn dirs(self) -> &'static [Dir] {
+        match self {
+            Connectivity::Four => &Dir::ORTHOGONAL,
+            Connectivity::Eight => &Dir::ALL,
+        }
+    }
+}
+
+/// A connected set of cells. Perimeter and sides are measured along cell edges,
+/// so cells only touching diagonally don't share any.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Region {
+    /// Row by row.
+    pub cells: Vec<Point>,
+    /// Cell edges with no cell of the region on the other side.
+    pub perimeter: usize,
+    /// Straight runs of perimeter, holes included.
+    pub sides: usize,
+    /// Top left and bottom right corners, inclusive.
+    pub bbox: (Point, Point),
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    // `cells` sorted, `inside` tells whether a point is in the region
+    fn measure<T>(grid: &Grid<T>, cells: Vec<Point>, inside: impl Fn(Point) -> bool) -> Region {
+        let is_in = |p: Point, d: Dir| grid.step(p, d).is_some_and(&inside);
+        let (mut perimeter, mut sides) = (0, 0);
+        let (mut min, mut max) = (cells[0], cells[0]);
+        for &p in &cells {
+            for d in Dir::ORTHOGONAL {
+                let e = d.turn_right();
+                let diagonal = grid.step(p, d.offset() + e.offset()).is_some_and(&inside);
+                perimeter += !is_in(p, d) as usize;
+                // a side ends at every outer and inner corner
+                sides += match (is_in(p, d), is_in(p, e)) {
+                    (false, false) => 1,
+                    (true, true) => !diagonal as usize,
+                    _ => 0,
+                };
+            }
+            min = Point::new(min.row.min(p.row), min.col.min(p.col));
+            max = Point::new(max.row.max(p.row), max.col.max(p.col));
+        }
+        Region {
+            cells,
+            perimeter,
+            sides,
+            bbox: (min, max),
+        }
+    }
+}
+
+/// Every region of a grid with the label of each cell.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Regions {
+    /// The index in `regions` of each cell's region, `None` for cells left out.
+    pub labels: Grid<Option<usize>>,
+    /// Ordered by their first cell, row by row.
+    pub regions: Vec<Region>,
+}
+
+impl Regions {
+    pub fn region_of(&self, p: Point) -> Option<&Region> {
+        self.labels
+            .get(p)
+            .copied()
+            .flatten()
+            .map(|i| &self.regions[i])
+    }
+}
+
+impl<T> Grid<T> {
+    /// The region around `start`, stepping between neighbouring cells `a` and `b`
+    /// where `joins(a, b)`.
+    pub fn flood_fill(
+        &self,
+        start: Point,
+        conn: Connectivity,
+        joins: impl Fn(&T, &T) -> bool,
+    ) -> Region {
+        let mut labels = self.map(|_| None);
+        let cells = self.fill(start, conn, 0, &mut labels, &joins);
+        Region::measure(self, cells, |p| labels[p].is_some())
+    }
+
+    /// Regions of equal neighbouring cells, covering the whole grid.
+    pub fn regions(&self, conn: Connectivity) -> Regions
+    where
+        T: PartialEq,
+    {
+        self.label(conn, |_| true, |a, b| a == b)
+    }
+
+    /// Regions of neighbouring cells where `inside` holds, other cells are left out.
+    pub fn regions_where(&self, conn: Connectivity, inside: impl Fn(&T) -> bool) -> Regions {
+        self.label(conn, &inside, |a, b| inside(a) && inside(b))
+    }
+
+    fn label(
+        &self,
+        conn: Connectivity,
+        include: impl Fn(&T) -> bool,
+        joins: impl Fn(&T, &T) -> bool,
+    ) -> Regions {
+        let mut labels = self.map(|_| None);
+        let mut found = Vec::new();
+        for p in self.positions() {
+            if labels[p].is_none() && include(&self[p]) {
+                found.push(self.fill(p, conn, found.len(), &mut labels, &joins));
+            }
+        }
+        let regions = found
+            .into_iter()
+            .enumerate()
+            .map(|(i, cells)| Region::measure(self, cells, |p| labels[p] == Some(i)))
+            .collect();
+        Regions { labels, regions }
+    }
+
+    // labels the unlabeled cells joined to `start` with `id`, returns them sorted
+    fn fill(
+        &self,
+        start: Point,
+        conn: Connectivity,
+        id: usize,
+        labels: &mut Grid<Option<usize>>,
+        joins: impl Fn(&T, &T) -> bool,
+    ) -> Vec<Point> {
+        let mut cells = vec![start];
+        let mut todo = vec![start];
+        labels[start] = Some(id);
+        while let Some(p) = todo.pop() {
+            for (_, q) in self.neighbors(p, conn.dirs()) {
+                if labels[q].is_none() && joins(&self[p], &self[q]) {
+                    labels[q] = Some(id);
+                    cells.push(q);
+                    todo.push(q);
+                }
+            }
+        }
+        cells.sort_unstable();
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the larger example of AoC 2024 day 12
+    const GARDEN: &str = "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE
+";
+
+    #[test]
+    fn regions_test() {
+        let garden: Grid<char> = GARDEN.parse().unwrap();
+        let regions = garden.regions(Connectivity::Four);
+        assert_eq!(11, regions.regions.len());
+        let price: usize = regions.regions.iter().map(|r| r.area() * r.perimeter).sum();
+        let bulk: usize = regions.regions.iter().map(|r| r.area() * r.sides).sum();
+        assert_eq!((1930, 1206), (price, bulk));
+
+        let r = regions.region_of(Point::new(0, 0)).unwrap();
+        assert_eq!((12, 18, 10), (r.area(), r.perimeter, r.sides));
+        assert_eq!((Point::new(0, 0), Point::new(3, 4)), r.bbox);
+        assert_eq!(Some(0), regions.labels[Point::new(3, 2)]);
+    }
+
+    #[test]
+    fn connectivity_test() {
+        // an O with a hole, and an X only joined diagonally
+        let grid: Grid<char> = "ooo.x.x\no.o..x.\nooo.x.x\n".parse().unwrap();
+        let o = grid.flood_fill(Point::new(0, 0), Connectivity::Four, |a, b| a == b);
+        assert_eq!((8, 16, 8), (o.area(), o.perimeter, o.sides));
+
+        let xs = grid.regions_where(Connectivity::Four, |&c| c == 'x');
+        assert_eq!(5, xs.regions.len());
+        assert_eq!(None, xs.labels[Point::new(0, 0)]);
+        let xs = grid.regions_where(Connectivity::Eight, |&c| c == 'x');
+        assert_eq!(1, xs.regions.len());
+        let x = &xs.regions[0];
+        assert_eq!((5, 20, 20), (x.area(), x.perimeter, x.sides));
+        assert_eq!((Point::new(0, 4), Point::new(2, 6)), x.bbox);
+
+        // the hole in the O is a region of its own
+        let all = grid.regions(Connectivity::Eight);
+        assert_eq!(4, all.regions.len());
+        assert_eq!(1, all.region_of(Point::new(1, 1)).unwrap().area());
+        assert_eq!(Some(&o), all.region_of(Point::new(2, 2)));
+    }
+}
@@ -0,0 +1,257 @@
+//! Terminal drawings of grids.
+//!
+//! A [`Renderer`] draws every cell of a grid as a styled character, then
+//! overlays paths, highlighted points and labels on top. Colour escapes are only
+//! written when stdout is a terminal, so the same drawing reads fine in test
+//! output, logs and pipes.
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write;
+use std::io::{self, IsTerminal};
+
+use crate::common::color::Rgb;
+use crate::common::{Dir, Grid, Point, Vec2};
+
+/// How a cell is drawn, the terminal's defaults where `None`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Style {
+    pub fg: Option<Rgb>,
+    pub bg: Option<Rgb>,
+    pub bold: bool,
+}
+
+impl Style {
+    pub const PLAIN: Style = Style {
+        fg: None,
+        bg: None,
+        bold: false,
+    };
+
+    pub const fn fg(color: Rgb) -> Style {
+        Style {
+            fg: Some(color),
+            ..Style::PLAIN
+        }
+    }
+
+    /// This style on a `color` background.
+    pub const fn on(self, color: Rgb) -> Style {
+        Style {
+            bg: Some(color),
+            ..self
+        }
+    }
+
+    pub const fn bold(self) -> Style {
+        Style { bold: true, ..self }
+    }
+
+    // resets, then sets every attribute of the style
+    fn escape(self) -> String {
+        let mut s = String::from("\x1b[0");
+        if self.bold {
+            s.push_str(";1");
+        }
+        if let Some(Rgb(r, g, b)) = self.fg {
+            let _ = write!(s, ";38;2;{};{};{}", r, g, b);
+        }
+        if let Some(Rgb(r, g, b)) = self.bg {
+            let _ = write!(s, ";48;2;{};{};{}", r, g, b);
+        }
+        s.push('m');
+        s
+    }
+}
+
+/// Colour for stdout, when it is a terminal and `NO_COLOR` isn't set.
+pub fn use_color() -> bool {
+    io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none()
+}
+
+// the character and style of a cell
+type CellFn<'a, T> = dyn Fn(Point, &T) -> (char, Style) + 'a;
+
+/// Draws a grid, or the part of it in a viewport, as lines of text.
+pub struct Renderer<'a, T> {
+    grid: &'a Grid<T>,
+    cell: Box<CellFn<'a, T>>,
+    // a replacement character, or none to keep the cell's
+    overlay: HashMap<Point, (Option<char>, Style)>,
+    top_left: Point,
+    rows: usize,
+    cols: usize,
+    color: bool,
+}
+
+impl<'a, T> Renderer<'a, T> {
+    /// Draws each cell the way `cell` says, in colour when [`use_color`].
+    pub fn new(grid: &'a Grid<T>, cell: impl Fn(Point, &T) -> (char, Style) + 'a) -> Self {
+        Renderer {
+            grid,
+            cell: Box::new(cell),
+            overlay: HashMap::new(),
+            top_left: Point::default(),
+            rows: grid.rows,
+            cols: grid.cols,
+            color: use_color(),
+        }
+    }
+
+    /// Restyles `points`, keeping their characters.
+    pub fn highlight(mut self, points: impl IntoIterator<Item = Point>, style: Style) -> Self {
+        for p in points {
+            self.overlay.entry(p).or_insert((None, style)).1 = style;
+        }
+        self
+    }
+
+    /// Draws `ch` at `points`.
+    pub fn mark(mut self, points: impl IntoIterator<Item = Point>, ch: char, style: Style) -> Self {
+        for p in points {
+            self.overlay.insert(p, (Some(ch), style));
+        }
+        self
+    }
+
+    /// Draws an arrow at each point of `path` towards the next one, the last point
+    /// keeps its character.
+    pub fn path(mut self, path: &[Point], style: Style) -> Self {
+        for w in path.windows(2) {
+            self.overlay.insert(w[0], (Some(arrow(w[1] - w[0])), style));
+        }
+        self.highlight(path.last().copied(), style)
+    }
+
+    /// Writes `text` from `p` to the right, cut off at the edge of the grid.
+    pub fn label(mut self, p: Point, text: &str, style: Style) -> Self {
+        for (q, ch) in self.grid.ray(p, Dir::E).zip(text.chars()) {
+            self.overlay.insert(q, (Some(ch), style));
+        }
+        self
+    }
+
+    /// Only draws `rows` x `cols` cells from `top_left`, as far as the grid goes.
+    pub fn viewport(mut self, top_left: Point, rows: usize, cols: usize) -> Self {
+        self.top_left = Point::new(
+            top_left.row.min(self.grid.rows),
+            top_left.col.min(self.grid.cols),
+        );
+        self.rows = rows.min(self.grid.rows - self.top_left.row);
+        self.cols = cols.min(self.grid.cols - self.top_left.col);
+        self
+    }
+
+    /// A viewport of `rows` x `cols` cells around `center`, moved inside the grid
+    /// near the edges.
+    pub fn around(self, center: Point, rows: usize, cols: usize) -> Self {
+        let start =
+            |c: usize, n: usize, len: usize| c.saturating_sub(n / 2).min(len.saturating_sub(n));
+        let top_left = Point::new(
+            start(center.row, rows, self.grid.rows),
+            start(center.col, cols, self.grid.cols),
+        );
+        self.viewport(top_left, rows, cols)
+    }
+
+    /// Colour escapes or plain text, whatever stdout is.
+    pub fn color(mut self, on: bool) -> Self {
+        self.color = on;
+        self
+    }
+
+    pub fn render(&self) -> String {
+        let mut s = String::new();
+        for row in self.top_left.row..self.top_left.row + self.rows {
+            let mut last = None;
+            for col in self.top_left.col..self.top_left.col + self.cols {
+                let p = Point::new(row, col);
+                let (mut ch, mut style) = (self.cell)(p, &self.grid[p]);
+                if let Some(&(over, over_style)) = self.overlay.get(&p) {
+                    ch = over.unwrap_or(ch);
+                    style = over_style;
+                }
+                if self.color && last != Some(style) {
+                    s.push_str(&style.escape());
+                    last = Some(style);
+                }
+                s.push(ch);
+            }
+            if self.color {
+                s.push_str("\x1b[0m");
+            }
+            s.push('\n');
+        }
+        s
+    }
+
+    pub fn print(&self) {
+        print!("{}", self.render());
+    }
+}
+
+impl<'a> Renderer<'a, char> {
+    /// The grid's own characters, unstyled.
+    pub fn chars(grid: &'a Grid<char>) -> Self {
+        Renderer::new(grid, |_, &c| (c, Style::PLAIN))
+    }
+}
+
+// the step from one path point to the next
+fn arrow(step: Vec2) -> char {
+    match Dir::ALL.iter().find(|d| d.offset() == step) {
+        Some(Dir::N) => '^',
+        Some(Dir::E) => '>',
+        Some(Dir::S) => 'v',
+        Some(Dir::W) => '<',
+        Some(Dir::Ne | Dir::Sw) => '/',
+        Some(Dir::Se | Dir::Nw) => '\\',
+        None => '*',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::color::{PATH, WALL};
+
+    fn grid() -> Grid<char> {
+        "#....\n.#...\n...#.\n".parse().unwrap()
+    }
+
+    #[test]
+    fn overlay_test() {
+        let grid = grid();
+        let p = |r, c| Point::new(r, c);
+        let path = [p(0, 1), p(0, 2), p(1, 2), p(2, 2), p(2, 1)];
+        let s = Renderer::chars(&grid)
+            .color(false)
+            .path(&path, Style::fg(PATH))
+            .mark([p(2, 4)], 'G', Style::PLAIN)
+            .label(p(1, 3), "abc", Style::PLAIN)
+            .render();
+        assert_eq!("#>v..\n.#vab\n..<#G\n", s);
+
+        let view = Renderer::chars(&grid).color(false);
+        assert_eq!("#..\n..#\n", view.viewport(p(1, 1), 5, 3).render());
+        let view = Renderer::chars(&grid).color(false);
+        assert_eq!("..\n..\n", view.around(p(0, 4), 2, 2).render());
+    }
+
+    #[test]
+    fn color_test() {
+        let grid = grid();
+        let walls = |_, &c: &char| match c {
+            '#' => (c, Style::fg(WALL).bold()),
+            _ => (c, Style::PLAIN),
+        };
+        let s = Renderer::new(&grid, walls)
+            .color(true)
+            .viewport(Point::new(0, 0), 1, 3)
+            .highlight([Point::new(0, 2)], Style::PLAIN.on(PATH))
+            .render();
+        assert_eq!(
+            "\x1b[0;1;38;2;150;150;160m#\x1b[0m.\x1b[0;48;2;240;200;60m.\x1b[0m\n",
+            s
+        );
+    }
+}
@@ -0,0 +1,374 @@
+//! Shortest path searches over any graph given as a neighbours function, and over
+//! [`Grid`]s taking orthogonal steps.
+//!
+//! Every search starts from one or more nodes and stops at the first node where
+//! `is_goal` holds, pass `|_| false` to reach everything reachable.
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+use crate::common::{Grid, Point};
+
+/// What a search found.
+#[derive(Clone, Debug)]
+pub struct Search<N> {
+    /// Distance of every reached node from the nearest start.
+    pub dist: HashMap<N, u64>,
+    /// The nodes each reached node is entered from on a shortest path. Only
+    /// [`all_shortest_paths`] keeps more than one.
+    pub preds: HashMap<N, Vec<N>>,
+    /// Nodes in the order they were expanded.
+    pub order: Vec<N>,
+    /// The first goal expanded, if any.
+    pub goal: Option<N>,
+}
+
+impl<N> Default for Search<N> {
+    fn default() -> Self {
+        Search {
+            dist: HashMap::new(),
+            preds: HashMap::new(),
+            order: Vec::new(),
+            goal: None,
+        }
+    }
+}
+
+impl<N: Copy + Eq + Hash> Search<N> {
+    pub fn distance(&self, n: N) -> Option<u64> {
+        self.dist.get(&n).copied()
+    }
+
+    /// A shortest path from a start to `n`, both included.
+    pub fn path_to(&self, n: N) -> Option<Vec<N>> {
+        self.dist.get(&n)?;
+        let mut path = vec![n];
+        while let Some(&p) = self.preds.get(path.last()?).and_then(|ps| ps.first()) {
+            path.push(p);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// A shortest path to the goal.
+    pub fn path(&self) -> Option<Vec<N>> {
+        self.path_to(self.goal?)
+    }
+
+    /// Every node on some shortest path to `n`, `n` included.
+    pub fn on_paths_to(&self, n: N) -> HashSet<N> {
+        let mut seen = HashSet::new();
+        let mut todo = vec![n];
+        while let Some(m) = todo.pop() {
+            if self.dist.contains_key(&m) && seen.insert(m) {
+                todo.extend(self.preds.get(&m).into_iter().flatten());
+            }
+        }
+        seen
+    }
+
+    /// The number of shortest paths to `n`, counted over the predecessor DAG of
+    /// [`all_shortest_paths`] with edges of weight 1 or more.
+    pub fn count_paths(&self, n: N) -> u64 {
+        // expansion order has predecessors first
+        let mut counts: HashMap<N, u64> = HashMap::new();
+        for &m in &self.order {
+            let count = match self.preds.get(&m) {
+                Some(ps) => ps.iter().map(|p| counts.get(p).copied().unwrap_or(0)).sum(),
+                None => 1,
+            };
+            counts.insert(m, count);
+            if m == n {
+                return count;
+            }
+        }
+        0
+    }
+}
+
+/// Breadth first search, every edge has weight 1.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(N) -> I,
+    mut is_goal: impl FnMut(N) -> bool,
+) -> Search<N>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut s = Search::default();
+    let mut queue = VecDeque::new();
+    for n in starts {
+        if s.dist.insert(n, 0).is_none() {
+            queue.push_back(n);
+        }
+    }
+    while let Some(n) = queue.pop_front() {
+        s.order.push(n);
+        if is_goal(n) {
+            s.goal = Some(n);
+            break;
+        }
+        let d = s.dist[&n] + 1;
+        for m in neighbors(n) {
+            if let Entry::Vacant(e) = s.dist.entry(m) {
+                e.insert(d);
+                s.preds.insert(m, vec![n]);
+                queue.push_back(m);
+            }
+        }
+    }
+    s
+}
+
+/// Dijkstra's search, `neighbors` gives (node, edge weight) pairs.
+pub fn dijkstra<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(N) -> I,
+    is_goal: impl FnMut(N) -> bool,
+) -> Search<N>
+where
+    N: Copy + Eq + Hash + Ord,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    astar(starts, neighbors, |_| 0, is_goal)
+}
+
+/// A* search, `heuristic` must never overestimate the distance left to a goal.
+/// Nodes are expanded at most once when it is also consistent, e.g. [`manhattan`]
+/// on a grid with steps of weight 1 or more.
+pub fn astar<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(N) -> I,
+    mut heuristic: impl FnMut(N) -> u64,
+    mut is_goal: impl FnMut(N) -> bool,
+) -> Search<N>
+where
+    N: Copy + Eq + Hash + Ord,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    let mut s = Search::default();
+    let mut heap = BinaryHeap::new();
+    for n in starts {
+        if s.dist.insert(n, 0).is_none() {
+            heap.push(Reverse((heuristic(n), 0, n)));
+        }
+    }
+    while let Some(Reverse((_, g, n))) = heap.pop() {
+        // a shorter way here was found after this entry was pushed
+        if g > s.dist[&n] {
+            continue;
+        }
+        s.order.push(n);
+        if is_goal(n) {
+            s.goal = Some(n);
+            break;
+        }
+        for (m, w) in neighbors(n) {
+            let d = g + w;
+            if s.dist.get(&m).is_some_and(|&old| old <= d) {
+                continue;
+            }
+            s.dist.insert(m, d);
+            s.preds.insert(m, vec![n]);
+            heap.push(Reverse((d + heuristic(m), d, m)));
+        }
+    }
+    s
+}
+
+/// Dijkstra's search keeping every shortest way into each node, so `preds` is the
+/// DAG of all shortest paths. Goes on past the first goal until every node as far
+/// away as it is expanded.
+pub fn all_shortest_paths<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(N) -> I,
+    mut is_goal: impl FnMut(N) -> bool,
+) -> Search<N>
+where
+    N: Copy + Eq + Hash + Ord,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    let mut s = Search::default();
+    let mut heap = BinaryHeap::new();
+    for n in starts {
+        if s.dist.insert(n, 0).is_none() {
+            heap.push(Reverse((0, n)));
+        }
+    }
+    while let Some(Reverse((g, n))) = heap.pop() {
+        if g > s.dist[&n] {
+            continue;
+        }
+        if s.goal.is_some_and(|goal| g > s.dist[&goal]) {
+            break;
+        }
+        s.order.push(n);
+        if s.goal.is_none() && is_goal(n) {
+            s.goal = Some(n);
+        }
+        for (m, w) in neighbors(n) {
+            let d = g + w;
+            match s.dist.get(&m) {
+                Some(&old) if old < d => (),
+                Some(&old) if old == d => s.preds.entry(m).or_default().push(n),
+                _ => {
+                    s.dist.insert(m, d);
+                    s.preds.insert(m, vec![n]);
+                    heap.push(Reverse((d, m)));
+                }
+            }
+        }
+    }
+    s
+}
+
+/// Steps along rows and columns to `goal`, an A* heuristic for orthogonal moves.
+pub fn manhattan(goal: Point) -> impl Fn(Point) -> u64 {
+    move |p| p.manhattan(goal) as u64
+}
+
+/// King moves to `goal`, an A* heuristic when diagonal steps are allowed.
+pub fn chebyshev(goal: Point) -> impl Fn(Point) -> u64 {
+    move |p| p.chebyshev(goal) as u64
+}
+
+/// Searches taking orthogonal steps between cells.
+impl<T> Grid<T> {
+    /// Breadth first search stepping onto cells where `passable` holds.
+    pub fn bfs(
+        &self,
+        start: Point,
+        passable: impl Fn(&T) -> bool,
+        is_goal: impl FnMut(Point) -> bool,
+    ) -> Search<Point> {
+        let passable = &passable;
+        let steps = |p| self.neighbors_4(p).filter(move |&q| passable(&self[q]));
+        bfs([start], steps, is_goal)
+    }
+
+    /// Dijkstra's search, `cost` of stepping onto a cell, `None` where impassable.
+    pub fn dijkstra(
+        &self,
+        start: Point,
+        cost: impl Fn(&T) -> Option<u64>,
+        is_goal: impl FnMut(Point) -> bool,
+    ) -> Search<Point> {
+        let cost = &cost;
+        let steps = |p| {
+            self.neighbors_4(p)
+                .filter_map(move |q| cost(&self[q]).map(|w| (q, w)))
+        };
+        dijkstra([start], steps, is_goal)
+    }
+
+    /// A* search to `goal` with the [`manhattan`] heuristic, every `cost` must be 1
+    /// or more.
+    pub fn astar(
+        &self,
+        start: Point,
+        goal: Point,
+        cost: impl Fn(&T) -> Option<u64>,
+    ) -> Search<Point> {
+        let cost = &cost;
+        let steps = |p| {
+            self.neighbors_4(p)
+                .filter_map(move |q| cost(&self[q]).map(|w| (q, w)))
+        };
+        astar([start], steps, manhattan(goal), |p| p == goal)
+    }
+
+    /// Every shortest path, stepping onto cells where `passable` holds.
+    pub fn all_shortest_paths(
+        &self,
+        start: Point,
+        passable: impl Fn(&T) -> bool,
+        is_goal: impl FnMut(Point) -> bool,
+    ) -> Search<Point> {
+        let passable = &passable;
+        let steps = |p| {
+            self.neighbors_4(p)
+                .filter(move |&q| passable(&self[q]))
+                .map(|q| (q, 1))
+        };
+        all_shortest_paths([start], steps, is_goal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#...
+.##...#E
+";
+
+    #[test]
+    fn grid_test() {
+        let maze: Grid<char> = MAZE.parse().unwrap();
+        let (start, end) = (Point::new(0, 0), Point::new(3, 7));
+        let open = |&c: &char| c != '#';
+
+        let s = maze.bfs(start, open, |p| p == end);
+        assert_eq!(Some(end), s.goal);
+        assert_eq!(Some(12), s.distance(end));
+        let path = s.path().unwrap();
+        assert_eq!(13, path.len());
+        assert_eq!((start, end), (path[0], path[12]));
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+        assert!(path.iter().all(|&p| maze[p] != '#'));
+        assert_eq!(Some(&start), s.order.first());
+
+        let cost = |&c: &char| (c != '#').then_some(1);
+        assert_eq!(
+            Some(12),
+            maze.dijkstra(start, cost, |p| p == end).distance(end)
+        );
+        let s = maze.astar(start, end, cost);
+        assert_eq!(Some(12), s.distance(end));
+        assert_eq!(13, s.path().unwrap().len());
+
+        // unreachable goals are never found
+        let s = maze.bfs(start, |&c| c == 'S' || c == '.', |p| p == end);
+        assert_eq!((None, None), (s.goal, s.path()));
+    }
+
+    #[test]
+    fn all_shortest_paths_test() {
+        let open: Grid<char> = "...\n...\n...\n".parse().unwrap();
+        let end = Point::new(2, 2);
+        let s = open.all_shortest_paths(Point::new(0, 0), |_| true, |p| p == end);
+        assert_eq!(Some(4), s.distance(end));
+        assert_eq!(6, s.count_paths(end));
+        assert_eq!(9, s.on_paths_to(end).len());
+        assert_eq!(1, s.on_paths_to(Point::new(0, 0)).len());
+        assert_eq!(2, s.count_paths(Point::new(1, 1)));
+    }
+
+    #[test]
+    fn graph_test() {
+        // 0 -> 1 -> 3 costs 5, 0 -> 2 -> 3 costs 5, 0 -> 3 costs 7
+        let edges = |n: u8| match n {
+            0 => vec![(1, 1), (2, 4), (3, 7)],
+            1 => vec![(3, 4)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        };
+        let s = dijkstra([0], edges, |n| n == 3);
+        assert_eq!(Some(5), s.distance(3));
+        assert_eq!(Some(vec![0, 1, 3]), s.path());
+        assert_eq!(vec![0, 1, 2, 3], s.order);
+
+        let s = all_shortest_paths([0], edges, |n| n == 3);
+        assert_eq!(2, s.count_paths(3));
+        assert_eq!(HashSet::from([0, 1, 2, 3]), s.on_paths_to(3));
+
+        let s = bfs([0], |n| edges(n).into_iter().map(|(m, _)| m), |_| false);
+        assert_eq!(Some(1), s.distance(3));
+        assert_eq!(4, s.order.len());
+    }
+}
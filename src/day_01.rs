@@ -1,78 +1,91 @@
-use crate::common::Solution;
+use crate::common::{Solution, Timings};
+use crate::error::{column, parse_number, ParseError, ParseResult, Result};
+use crate::input::load_input;
 use std::time::Instant;
 
-static INPUT: &str = include_str!("../data/day_01.txt");
-
-pub fn solve_day_01() -> Solution {
+pub fn solve_day_01() -> Result<Solution> {
+    let input = load_input(1)?;
     let start = Instant::now();
-    let (ls, rs) = parse_input(INPUT);
+    let (ls, rs) = parse(&input).map_err(|e| e.in_day(1))?;
+    let parsed = start.elapsed();
     let part_a = day_01_a(&ls, &rs); // 2086478
+    let solved_a = start.elapsed();
     let part_b = day_01_b(&ls, &rs); // 24941624
     let duration = start.elapsed();
-    Solution {
+    Ok(Solution {
         part_a,
         part_b,
         duration,
-    }
+        timings: Timings::from_marks(parsed, solved_a, duration),
+    })
+}
+
+pub fn part_a(input: &str) -> Result<String> {
+    let (ls, rs) = parse(input).map_err(|e| e.in_day(1))?;
+    Ok(day_01_a(&ls, &rs))
+}
+
+pub fn part_b(input: &str) -> Result<String> {
+    let (ls, rs) = parse(input).map_err(|e| e.in_day(1))?;
+    Ok(day_01_b(&ls, &rs))
 }
 
-fn day_01_a(_ls: &[i32], _rs: &[i32]) -> String {
+pub fn day_01_a(_ls: &[i32], _rs: &[i32]) -> String {
     let mut ls = _ls.to_vec();
     ls.sort_unstable();
     let mut rs = _rs.to_vec();
     rs.sort_unstable();
-    let v = ls.iter().zip(rs).map(|(&l, r)| (l - r).abs()).sum::<i32>();
+    let v = ls
+        .iter()
+        .zip(rs)
+        .map(|(&l, r)| l.abs_diff(r) as u64)
+        .sum::<u64>();
     format!("day_01_a = {}", v)
 }
 
-fn day_01_b(ls: &[i32], rs: &[i32]) -> String {
+pub fn day_01_b(ls: &[i32], rs: &[i32]) -> String {
     let mut score = 0;
     for &l in ls {
-        score += rs.iter().filter(|&&r| r == l).sum::<i32>();
+        score += rs
+            .iter()
+            .filter(|&&r| r == l)
+            .map(|&r| r as i64)
+            .sum::<i64>();
     }
     format!("day_01_b = {}", score)
 }
 
-fn parse_input(input: &str) -> (Vec<i32>, Vec<i32>) {
-    input
-        .split("\n")
-        .filter(|s| !s.is_empty())
-        .map(|s| {
-            let mut ss = s.split(" ").filter(|s| !s.is_empty());
-            let x = ss.next().unwrap().parse::<i32>().unwrap();
-            let y = ss.next().unwrap().parse::<i32>().unwrap();
-            (x, y)
-        })
-        .unzip()
+pub fn parse(input: &str) -> ParseResult<(Vec<i32>, Vec<i32>)> {
+    let mut ls = Vec::new();
+    let mut rs = Vec::new();
+    for (i, s) in input.lines().enumerate().filter(|(_, s)| !s.is_empty()) {
+        let mut ss = s.split(' ').filter(|s| !s.is_empty());
+        match (ss.next(), ss.next(), ss.next()) {
+            (Some(x), Some(y), None) => {
+                ls.push(parse_number(i + 1, s, x)?);
+                rs.push(parse_number(i + 1, s, y)?);
+            }
+            (_, _, Some(z)) => {
+                return Err(ParseError::new(i + 1, column(s, z), "expected two numbers"));
+            }
+            _ => return Err(ParseError::new(i + 1, 1, "expected two numbers")),
+        }
+    }
+    Ok((ls, rs))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    static STR: &str = r"3   4
-4   3
-2   5
-1   3
-3   9
-3   3";
-
-    #[test]
-    fn a_test() {
-        let (ls, rs) = parse_input(STR);
-        println!("{:?}", &ls);
-        println!("{:?}", &rs);
-
-        let result = day_01_a(&ls, &rs);
-        println!("{}", &result);
-        assert_eq!("day_01_a = 11".to_string(), result);
-    }
-
     #[test]
-    fn b_test() {
-        let (ls, rs) = parse_input(STR);
-        let result = day_01_b(&ls, &rs);
-        println!("{}", &result);
-        assert_eq!("day_01_b = 31".to_string(), result);
+    fn extremes_test() {
+        let (ls, rs) = ([i32::MIN, i32::MIN], [i32::MAX, i32::MAX]);
+        assert_eq!("day_01_a = 8589934590", day_01_a(&ls, &rs));
+        assert_eq!("day_01_b = 0", day_01_b(&ls, &rs));
+        assert_eq!(
+            "day_01_b = 6442450941",
+            day_01_b(&[i32::MAX], &[i32::MAX; 3])
+        );
     }
 }
@@ -1,29 +1,41 @@
-use crate::common::Solution;
+use crate::common::{Solution, Timings};
+use crate::error::{parse_number, ParseResult, Result};
+use crate::input::load_input;
 use std::time::Instant;
 
-static INPUT: &str = include_str!("../data/day_02.txt");
-
-pub fn solve_day_02() -> Solution {
+pub fn solve_day_02() -> Result<Solution> {
+    let input = load_input(2)?;
     let start = Instant::now();
-    let xss = parse_input(INPUT);
+    let xss = parse(&input).map_err(|e| e.in_day(2))?;
+    let parsed = start.elapsed();
     let part_a = day_02_a(&xss); // 390
+    let solved_a = start.elapsed();
     let part_b = day_02_b(&xss); // 439
     let duration = start.elapsed();
-    Solution {
+    Ok(Solution {
         part_a,
         part_b,
         duration,
-    }
+        timings: Timings::from_marks(parsed, solved_a, duration),
+    })
 }
 
-fn day_02_a(xs: &[Vec<i16>]) -> String {
+pub fn part_a(input: &str) -> Result<String> {
+    Ok(day_02_a(&parse(input).map_err(|e| e.in_day(2))?))
+}
+
+pub fn part_b(input: &str) -> Result<String> {
+    Ok(day_02_b(&parse(input).map_err(|e| e.in_day(2))?))
+}
+
+pub fn day_02_a(xs: &[Vec<i16>]) -> String {
     let v = xs
         .iter()
         .fold(0, |acc, v| acc + if is_safe(v) { 1 } else { 0 });
     format!("day_02_a = {}", v)
 }
 
-fn day_02_b(xss: &[Vec<i16>]) -> String {
+pub fn day_02_b(xss: &[Vec<i16>]) -> String {
     let mut score = 0;
     for xs in xss {
         if is_safe(xs) {
@@ -43,6 +55,9 @@ fn day_02_b(xss: &[Vec<i16>]) -> String {
 }
 
 fn is_safe(xs: &[i16]) -> bool {
+    if xs.len() < 2 {
+        return true;
+    }
     let increasing = xs[0] < xs[1];
     let decreasing = xs[0] > xs[1];
     let mut it = xs.iter().peekable();
@@ -50,7 +65,7 @@ fn is_safe(xs: &[i16]) -> bool {
         if let Some(_next_x) = it.peek() {
             let next_x = *_next_x;
             let same_direction = increasing && next_x > x || decreasing && next_x < x;
-            let diff = (x - next_x).abs();
+            let diff = x.abs_diff(*next_x);
             let adjacent = diff > 0 && diff < 4;
             // println!(
             //     "x: {}, next_x: {}, adjacent: {}, same_direction: {}",
@@ -64,14 +79,15 @@ fn is_safe(xs: &[i16]) -> bool {
     true
 }
 
-fn parse_input(input: &str) -> Vec<Vec<i16>> {
+pub fn parse(input: &str) -> ParseResult<Vec<Vec<i16>>> {
     input
-        .split("\n")
-        .filter(|s| !s.is_empty())
-        .map(|s| {
-            s.split(" ")
-                .filter(|s| !s.is_empty())
-                .map(|s| s.parse::<i16>().unwrap())
+        .lines()
+        .enumerate()
+        .filter(|(_, s)| !s.is_empty())
+        .map(|(i, s)| {
+            s.split(' ')
+                .filter(|x| !x.is_empty())
+                .map(|x| parse_number(i + 1, s, x))
                 .collect()
         })
         .collect()
@@ -80,30 +96,57 @@ fn parse_input(input: &str) -> Vec<Vec<i16>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
-    static STR: &str = r"7 6 4 2 1
-1 2 7 8 9
-9 7 6 2 1
-1 3 2 4 5
-8 6 4 4 1
-1 3 6 7 9";
+    fn is_safe_naive(xs: &[i16]) -> bool {
+        let diffs: Vec<i16> = xs.windows(2).map(|w| w[1] - w[0]).collect();
+        diffs.iter().all(|d| (1..=3).contains(d)) || diffs.iter().all(|d| (-3..=-1).contains(d))
+    }
 
-    #[test]
-    fn a_test() {
-        let xss = parse_input(STR);
-        println!("{:?}", &xss);
+    // a report is tolerated when it is safe with at most one level removed
+    fn dampened_naive(xs: &[i16]) -> bool {
+        is_safe_naive(xs)
+            || (0..xs.len()).any(|i| {
+                let mut ys = xs.to_vec();
+                ys.remove(i);
+                is_safe_naive(&ys)
+            })
+    }
 
-        let result = day_02_a(&xss);
-        println!("{}", &result);
-        assert_eq!("day_02_a = 2".to_string(), result);
+    fn reports() -> impl Strategy<Value = Vec<Vec<i16>>> {
+        // small steps from a start level, so both safe and unsafe reports are common
+        let report = (1i16..100, prop::collection::vec(-4i16..5, 0..8)).prop_map(|(x, steps)| {
+            steps.iter().fold(vec![x], |mut xs, d| {
+                xs.push(xs[xs.len() - 1] + d);
+                xs
+            })
+        });
+        prop::collection::vec(report, 1..20)
     }
 
-    #[test]
-    fn b_test() {
-        let xss = parse_input(STR);
+    proptest! {
+        #[test]
+        fn day_02_a_oracle_test(xss in reports()) {
+            let expected = xss.iter().filter(|xs| is_safe_naive(xs)).count();
+            prop_assert_eq!(format!("day_02_a = {}", expected), day_02_a(&xss));
+        }
 
-        let result = day_02_b(&xss);
-        println!("{}", &result);
-        assert_eq!("day_02_b = 4".to_string(), result);
+        #[test]
+        fn day_02_b_oracle_test(xss in reports()) {
+            let expected = xss.iter().filter(|xs| dampened_naive(xs)).count();
+            prop_assert_eq!(format!("day_02_b = {}", expected), day_02_b(&xss));
+        }
+    }
+
+    #[test]
+    fn extremes_test() {
+        let xss = parse(
+            "32767 -32768
+-32768 -32767 -32766
+",
+        )
+        .unwrap();
+        assert_eq!("day_02_a = 1", day_02_a(&xss));
+        assert_eq!("day_02_b = 2", day_02_b(&xss));
     }
 }
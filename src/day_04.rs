@@ -1,110 +1,96 @@
-use crate::common;
-use crate::common::{directions, neighbors_8, pad_grid, Dir, Solution};
+use crate::common::{Dir, Grid, Point, Solution, Timings};
+use crate::error::{ParseResult, Result};
+use crate::input::load_input;
+use crate::viz::{Canvas, DIM, PATH};
 use std::time::Instant;
 
-static INPUT: &str = include_str!("../data/day_04.txt");
-
-pub fn solve_day_04() -> Solution {
+pub fn solve_day_04() -> Result<Solution> {
+    let input = load_input(4)?;
     let start = Instant::now();
-    let mut css = parse_input(INPUT);
-    pad_grid(&mut css, &'.');
-    let part_a = day_04_a(&css); // 2573
-    let part_b = day_04_b(&css); // 1850
+    let grid = parse(&input).map_err(|e| e.in_day(4))?;
+    let parsed = start.elapsed();
+    let part_a = day_04_a(&grid); // 2573
+    let solved_a = start.elapsed();
+    let part_b = day_04_b(&grid); // 1850
     let duration = start.elapsed();
-    Solution {
+    Ok(Solution {
         part_a,
         part_b,
         duration,
-    }
+        timings: Timings::from_marks(parsed, solved_a, duration),
+    })
 }
 
-fn day_04_a(css: &[Vec<char>]) -> String {
-    let x_indexes = get_ch_indexes(css, 'X');
-    let mut acc = 0;
-    for (x, y) in x_indexes {
-        for dir in directions() {
-            acc += find_xmas(css, x, y, 'X', &dir);
-        }
-    }
-    format!("day_04_a = {}", acc)
+pub fn part_a(input: &str) -> Result<String> {
+    Ok(day_04_a(&parse(input).map_err(|e| e.in_day(4))?))
 }
 
-fn day_04_b(css: &[Vec<char>]) -> String {
-    let v = get_ch_indexes(css, 'A')
-        .iter()
-        .fold(0, |acc, (x, y)| acc + find_xmas_b(css, *x, *y));
-
-    format!("day_04_b = {}", v)
+pub fn part_b(input: &str) -> Result<String> {
+    Ok(day_04_b(&parse(input).map_err(|e| e.in_day(4))?))
 }
 
-fn parse_input(s: &str) -> Vec<Vec<char>> {
-    s.lines().map(|x| x.chars().collect()).collect()
+pub fn day_04_a(grid: &Grid<char>) -> String {
+    // every line in all eight orientations, XMAS can't overlap itself
+    let v: usize = Dir::ALL
+        .iter()
+        .flat_map(|&d| grid.line_strings(d))
+        .map(|line| line.matches("XMAS").count())
+        .sum();
+    format!("day_04_a = {}", v)
 }
 
-fn get_ch_indexes(css: &[Vec<char>], ch: char) -> Vec<(usize, usize)> {
-    css.iter()
-        .enumerate()
-        .flat_map(|(i, cs)| {
-            cs.iter()
-                .enumerate()
-                .map(move |(j, c)| if *c == ch { Some((i, j)) } else { None })
-        })
-        .flatten()
-        .collect::<Vec<(usize, usize)>>()
+pub fn day_04_b(grid: &Grid<char>) -> String {
+    let v = grid
+        .find_all(&'A')
+        .into_iter()
+        .filter(|&p| is_x_mas(grid, p))
+        .count();
+    format!("day_04_b = {}", v)
 }
 
-pub fn find_xmas(css: &[Vec<char>], x: usize, y: usize, current_char: char, dir: &Dir) -> usize {
-    let (next_x, next_y, next_char) = common::neighbor(css, x, y, dir);
-    match next_char {
-        'M' if { current_char == 'X' } => find_xmas(css, next_x, next_y, 'M', dir),
-        'A' if { current_char == 'M' } => find_xmas(css, next_x, next_y, 'A', dir),
-        'S' if { current_char == 'A' } => 1,
-        _ => 0,
-    }
+pub fn parse(s: &str) -> ParseResult<Grid<char>> {
+    s.parse()
 }
 
-fn find_xmas_b(css: &[Vec<char>], x: usize, y: usize) -> usize {
-    let [(_, _), (_, ne), (_, _), (_, se), (_, _), (_, sw), (_, _), (_, nw)] =
-        neighbors_8(css, x, y);
-    if ((ne == 'M' && sw == 'S') || (ne == 'S' && sw == 'M'))
-        && ((nw == 'M' && se == 'S') || (nw == 'S' && se == 'M'))
-    {
-        1
-    } else {
-        0
+/// Highlights the letters of every XMAS found by part a.
+pub fn visualize(input: &str) -> Result<Canvas> {
+    let grid = parse(input).map_err(|e| e.in_day(4))?;
+    let mut canvas = Canvas::new(grid.rows, grid.cols);
+    for (p, &ch) in grid.iter() {
+        canvas.set(p.row, p.col, ch, DIM);
+    }
+    for p in grid.find_all(&'X') {
+        for d in Dir::ALL {
+            for q in xmas(&grid, p, d).into_iter().flatten() {
+                canvas.set(q.row, q.col, grid[q], PATH);
+            }
+        }
     }
+    Ok(canvas)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    static STR: &str = r"MMMSXXMASM
-MSAMXMSMSA
-AMXSXMAAMM
-MSAMASMSMX
-XMASAMXAMM
-XXAMMXXAMA
-SMSMSASXSS
-SAXAMASAAA
-MAMMMXMMMM
-MXMXAXMASX";
-
-    #[test]
-    fn a_test() {
-        let mut css = parse_input(STR);
-        pad_grid(&mut css, &'.');
-        let result = day_04_a(&css);
-        println!("{}", &result);
-        assert_eq!("day_04_a = 18".to_string(), result);
+/// The points of the XMAS starting at `p` in direction `d`, if there is one.
+pub fn xmas(grid: &Grid<char>, p: Point, d: Dir) -> Option<[Point; 4]> {
+    let mut points = [p; 4];
+    for (i, ch) in "XMAS".chars().enumerate() {
+        if i > 0 {
+            points[i] = grid.step(points[i - 1], d)?;
+        }
+        if grid[points[i]] != ch {
+            return None;
+        }
     }
+    Some(points)
+}
 
-    #[test]
-    fn b_test() {
-        let mut css = parse_input(STR);
-        pad_grid(&mut css, &'.');
-        let result = day_04_b(&css);
-        println!("{}", &result);
-        assert_eq!("day_04_b = 9".to_string(), result);
-    }
+// both diagonals through the 'A' at `p` read MAS or SAM
+fn is_x_mas(grid: &Grid<char>, p: Point) -> bool {
+    let at = |d: Dir| grid.step(p, d).map(|q| grid[q]);
+    let mas = |a, b| {
+        matches!(
+            (at(a), at(b)),
+            (Some('M'), Some('S')) | (Some('S'), Some('M'))
+        )
+    };
+    mas(Dir::Nw, Dir::Se) && mas(Dir::Ne, Dir::Sw)
 }
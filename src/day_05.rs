@@ -1,15 +1,15 @@
-use crate::common::Solution;
+use crate::common::{Solution, Timings};
+use crate::error::{column, parse_number, ParseError, ParseResult, Result};
+use crate::input::load_input;
 use std::cmp::Ordering;
-use std::collections::{hash_map, HashMap};
+use std::collections::HashMap;
 use std::time::Instant;
 
-static INPUT: &str = include_str!("../data/day_05.txt");
-
 #[derive(Debug, Clone, Eq)]
-struct Page {
-    num: u32,
-    successors: Vec<u32>,
-    predecessors: Vec<u32>,
+pub struct Page {
+    pub num: u32,
+    pub successors: Vec<u32>,
+    pub predecessors: Vec<u32>,
 }
 
 impl Ord for Page {
@@ -37,72 +37,75 @@ impl PartialEq for Page {
     }
 }
 
-pub fn solve_day_05() -> Solution {
+pub fn solve_day_05() -> Result<Solution> {
+    let input = load_input(5)?;
     let start = Instant::now();
-    let xss = parse_input(INPUT);
+    let xss = parse(&input).map_err(|e| e.in_day(5))?;
+    let parsed = start.elapsed();
     let part_a = day_05_a(&xss); // 6034
+    let solved_a = start.elapsed();
     let part_b = day_05_b(&xss); // 6305
     let duration = start.elapsed();
-    Solution {
+    Ok(Solution {
         part_a,
         part_b,
         duration,
-    }
+        timings: Timings::from_marks(parsed, solved_a, duration),
+    })
 }
 
-fn day_05_a(xss: &[Vec<Page>]) -> String {
-    let mut tot = 0;
+pub fn part_a(input: &str) -> Result<String> {
+    Ok(day_05_a(&parse(input).map_err(|e| e.in_day(5))?))
+}
+
+pub fn part_b(input: &str) -> Result<String> {
+    Ok(day_05_b(&parse(input).map_err(|e| e.in_day(5))?))
+}
+
+pub fn day_05_a(xss: &[Vec<Page>]) -> String {
+    let mut tot: u64 = 0;
     for xs in xss {
         let mut copy = xs.clone();
         copy.sort();
         if *xs == copy {
-            tot += xs[xs.len() / 2].num;
+            tot += u64::from(xs[xs.len() / 2].num);
         }
     }
 
     format!("day_05_a = {}", tot)
 }
 
-fn day_05_b(xss: &[Vec<Page>]) -> String {
-    let mut tot = 0;
+pub fn day_05_b(xss: &[Vec<Page>]) -> String {
+    let mut tot: u64 = 0;
     for xs in xss {
         let mut copy = xs.clone();
         copy.sort();
         if *xs != copy {
-            tot += copy[copy.len() / 2].num;
+            tot += u64::from(copy[copy.len() / 2].num);
         }
     }
     format!("day_05_b = {}", tot)
 }
 
-fn parse_input(input: &str) -> Vec<Vec<Page>> {
-    let mut it = input.lines();
+pub fn parse(input: &str) -> ParseResult<Vec<Vec<Page>>> {
+    let mut it = input.lines().enumerate();
     let mut m: HashMap<u32, Vec<u32>> = HashMap::new();
-    let mut all_nums: Vec<u32> = vec![];
-    // create a hashmap of rules
+    // create a hashmap of rules, every page number gets an entry
     loop {
-        let line = it.next().unwrap();
+        let Some((i, line)) = it.next() else {
+            let msg = "missing blank line between rules and updates";
+            return Err(ParseError::new(input.lines().count() + 1, 1, msg));
+        };
         if line.is_empty() {
             break;
         }
-        let ss: Vec<&str> = line.split("|").collect();
-        let k = ss[0].parse::<u32>().unwrap();
-        let v = ss[1].parse::<u32>().unwrap();
-        all_nums.push(k);
-        all_nums.push(v);
-        if let hash_map::Entry::Vacant(e) = m.entry(k) {
-            e.insert(vec![v]);
-        } else {
-            m.get_mut(&k).unwrap().push(v);
-        }
-    }
-
-    // find and add the smallest value to the map
-    all_nums.dedup();
-    for k in all_nums.iter() {
-        if !m.contains_key(k) {
-            m.insert(*k, vec![]);
-        }
+        let Some((l, r)) = line.split_once('|') else {
+            return Err(ParseError::new(i + 1, 1, "expected a rule 'X|Y'"));
+        };
+        let k = parse_number(i + 1, line, l)?;
+        let v = parse_number(i + 1, line, r)?;
+        m.entry(k).or_default().push(v);
+        m.entry(v).or_default();
     }
 
     // create the Page map
@@ -120,77 +123,45 @@ fn parse_input(input: &str) -> Vec<Vec<Page>> {
         })
         .collect();
 
-    // fill predecessors
-    for k in m.keys() {
-        let page = page_m.get(k).unwrap().clone();
-        for v in page.predecessors.iter() {
-            let page_v = page_m.get_mut(v).unwrap();
-            page_v.successors.push(page.num);
+    // fill successors
+    for (k, vs) in m.iter() {
+        for v in vs {
+            if let Some(page_v) = page_m.get_mut(v) {
+                page_v.successors.push(*k);
+            }
         }
     }
 
     // get puzzle data
-    let xss = it
-        .map(|s| {
-            s.split(",")
-                .map(|x| {
-                    let k = x.parse::<u32>().unwrap();
-                    page_m.get(&k).unwrap().clone()
+    let mut xss = Vec::new();
+    for (i, s) in it.filter(|(_, s)| !s.is_empty()) {
+        let xs = s
+            .split(',')
+            .map(|x| {
+                let k: u32 = parse_number(i + 1, s, x)?;
+                page_m.get(&k).cloned().ok_or_else(|| {
+                    let msg = format!("page {} not present in rules", k);
+                    ParseError::new(i + 1, column(s, x), msg)
                 })
-                .collect::<Vec<Page>>()
-        })
-        .collect::<Vec<Vec<Page>>>();
+            })
+            .collect::<ParseResult<Vec<Page>>>()?;
+        xss.push(xs);
+    }
 
-    xss
+    Ok(xss)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    static STR: &str = r"47|53
-97|13
-97|61
-97|47
-75|29
-61|13
-75|53
-29|13
-97|29
-53|29
-61|53
-97|53
-61|29
-47|13
-75|47
-97|75
-47|61
-75|61
-47|29
-75|13
-53|13
-
-75,47,61,53,29
-97,61,53,29,13
-75,29,13
-75,97,47,61,53
-61,13,29
-97,13,75,29,47";
-
-    #[test]
-    fn a_test() {
-        let xss = parse_input(STR);
-
-        let result = day_05_a(&xss);
-        println!("{}", &result);
-        assert_eq!("day_05_a = 143".to_string(), result);
-    }
-
     #[test]
-    fn b_test() {
-        let xss = parse_input(STR);
-        let result = day_05_b(&xss);
-        println!("{}", &result);
-        assert_eq!("day_05_b = 123".to_string(), result);
+    fn large_pages_test() {
+        // middle pages whose sum doesn't fit a u32
+        let input = "4000000000|4000000001\n\n4000000000\n4000000000\n\
+                     4000000001,4000000000\n4000000001,4000000000\n";
+        let xss = parse(input).unwrap();
+        assert_eq!("day_05_a = 8000000000", day_05_a(&xss));
+        assert_eq!("day_05_b = 8000000002", day_05_b(&xss));
     }
 }
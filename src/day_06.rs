@@ -1,316 +1,278 @@
-use crate::common::Solution;
+use crate::common::{Dir, Grid, Point, Solution, Timings};
+use crate::error::{AocError, ParseError, ParseResult, Result};
+use crate::input::load_input;
+use crate::viz::{Canvas, DIM, MARK, PATH, START, WALL};
 use rayon::prelude::*;
-use std::cmp::PartialEq;
 use std::time::Instant;
 
 #[derive(Clone, Debug)]
-struct Grid {
-    pub css: Vec<Vec<char>>,
-    pub start_row: usize,
-    pub start_col: usize,
-    pub rows: usize,
-    pub cols: usize,
+pub struct Lab {
+    pub map: Grid<char>,
+    pub start: Point,
 }
 
 #[derive(Clone, Debug)]
 struct State {
-    cur_row: usize,
-    cur_col: usize,
+    pos: Point,
     dir: Dir,
-    visits: Vec<(usize, usize)>,
+    // the guard has left the lab
+    done: bool,
+    visits: Vec<Point>,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-enum Dir {
-    Up,
-    Down,
-    Left,
-    Right,
-    Done,
-}
-
-static INPUT: &str = include_str!("../data/day_06.txt");
-
-pub fn solve_day_06() -> Solution {
+pub fn solve_day_06() -> Result<Solution> {
+    let input = load_input(6)?;
     let start = Instant::now();
-    let grid = parse(INPUT);
-    let (part_a, candidates) = day_06_a(&grid); // 5329
-    let part_b = day_06_b(&grid, &candidates); // 2162
+    let lab = parse(&input).map_err(|e| e.in_day(6))?;
+    let parsed = start.elapsed();
+    let (part_a, candidates) = day_06_a(&lab)?; // 5329
+    let solved_a = start.elapsed();
+    let part_b = day_06_b(&lab, &candidates); // 2162
     let duration = start.elapsed();
-    Solution {
+    Ok(Solution {
         part_a,
         part_b,
         duration,
-    }
+        timings: Timings::from_marks(parsed, solved_a, duration),
+    })
 }
 
-impl Grid {
-    #[allow(dead_code)]
-    fn show(&self) {
-        for xs in self.css.iter().take(self.rows) {
-            for c in xs.iter().take(self.cols) {
-                print!("{}", c);
-            }
-            println!();
-        }
-        println!(
-            "start row: {}, start coll: {}\n",
-            self.start_row, self.start_col
-        );
-    }
+pub fn part_a(input: &str) -> Result<String> {
+    Ok(day_06_a(&parse(input).map_err(|e| e.in_day(6))?)?.0)
+}
+
+pub fn part_b(input: &str) -> Result<String> {
+    let lab = parse(input).map_err(|e| e.in_day(6))?;
+    let (_, candidates) = day_06_a(&lab)?;
+    Ok(day_06_b(&lab, &candidates))
+}
 
+impl Lab {
+    // walks ahead to the next turn, or out of the lab
     fn walk_a(&self, state: &mut State) {
-        match state.dir {
-            Dir::Up => loop {
-                match self.css[state.cur_row - 1][state.cur_col] {
-                    '_' => {
-                        state.dir = Dir::Done;
-                        break;
-                    }
-                    '#' => {
-                        state.dir = Dir::Right;
-                        break;
-                    }
-                    _ => {
-                        state.visits.push((state.cur_row, state.cur_col));
-                        state.cur_row -= 1
-                    }
-                }
-            },
-            Dir::Down => loop {
-                match self.css[state.cur_row + 1][state.cur_col] {
-                    '_' => {
-                        state.dir = Dir::Done;
-                        break;
-                    }
-                    '#' => {
-                        state.dir = Dir::Left;
-                        break;
-                    }
-                    _ => {
-                        state.visits.push((state.cur_row, state.cur_col));
-                        state.cur_row += 1
-                    }
+        loop {
+            match self.map.step(state.pos, state.dir) {
+                None => {
+                    state.done = true;
+                    break;
                 }
-            },
-            Dir::Left => loop {
-                match self.css[state.cur_row][state.cur_col - 1] {
-                    '_' => {
-                        state.dir = Dir::Done;
-                        break;
-                    }
-                    '#' => {
-                        state.dir = Dir::Up;
-                        break;
-                    }
-                    _ => {
-                        state.visits.push((state.cur_row, state.cur_col));
-                        state.cur_col -= 1
-                    }
+                Some(p) if self.map[p] == '#' => {
+                    state.dir = state.dir.turn_right();
+                    break;
                 }
-            },
-            Dir::Right => loop {
-                match self.css[state.cur_row][state.cur_col + 1] {
-                    '_' => {
-                        state.dir = Dir::Done;
-                        break;
-                    }
-                    '#' => {
-                        state.dir = Dir::Down;
-                        break;
-                    }
-                    _ => {
-                        state.visits.push((state.cur_row, state.cur_col));
-                        state.cur_col += 1
-                    }
+                Some(p) => {
+                    state.visits.push(state.pos);
+                    state.pos = p;
                 }
-            },
-            Dir::Done => (),
+            }
         }
     }
 
-    fn walk_b(&self, state: &mut State, obstacle_row: usize, obstacle_col: usize) {
-        match state.dir {
-            Dir::Up => loop {
-                let c = self.css[state.cur_row - 1][state.cur_col];
-                if c == '_' {
-                    state.dir = Dir::Done;
+    // like walk_a with an extra obstacle, without recording visits
+    fn walk_b(&self, state: &mut State, obstacle: Point) {
+        loop {
+            match self.map.step(state.pos, state.dir) {
+                None => {
+                    state.done = true;
                     break;
-                } else if c == '#'
-                    || (state.cur_row - 1 == obstacle_row && state.cur_col == obstacle_col)
-                {
-                    state.dir = Dir::Right;
-                    break;
-                } else {
-                    state.cur_row -= 1;
                 }
-            },
-            Dir::Down => loop {
-                let c = self.css[state.cur_row + 1][state.cur_col];
-                if c == '_' {
-                    state.dir = Dir::Done;
-                    break;
-                } else if c == '#'
-                    || (state.cur_row + 1 == obstacle_row && state.cur_col == obstacle_col)
-                {
-                    state.dir = Dir::Left;
+                Some(p) if self.map[p] == '#' || p == obstacle => {
+                    state.dir = state.dir.turn_right();
                     break;
-                } else {
-                    state.cur_row += 1;
                 }
-            },
-            Dir::Left => loop {
-                let c = self.css[state.cur_row][state.cur_col - 1];
-                if c == '_' {
-                    state.dir = Dir::Done;
-                    break;
-                } else if c == '#'
-                    || (state.cur_row == obstacle_row && state.cur_col - 1 == obstacle_col)
-                {
-                    state.dir = Dir::Up;
-                    break;
-                } else {
-                    state.cur_col -= 1
-                }
-            },
-            Dir::Right => loop {
-                let c = self.css[state.cur_row][state.cur_col + 1];
-                if c == '_' {
-                    state.dir = Dir::Done;
-                    break;
-                } else if c == '#'
-                    || (state.cur_row == obstacle_row && state.cur_col + 1 == obstacle_col)
-                {
-                    state.dir = Dir::Down;
-                    break;
-                } else {
-                    state.cur_col += 1;
-                }
-            },
-            Dir::Done => (),
+                Some(p) => state.pos = p,
+            }
         }
     }
 }
 
-fn day_06_a(grid: &Grid) -> (String, Vec<(usize, usize)>) {
+pub fn day_06_a(lab: &Lab) -> Result<(String, Vec<Point>)> {
     let mut state = State {
-        cur_row: grid.start_row,
-        cur_col: grid.start_col,
-        dir: Dir::Up,
+        pos: lab.start,
+        dir: Dir::N,
+        done: false,
         visits: Vec::new(),
     };
 
-    while state.dir != Dir::Done {
-        grid.walk_a(&mut state);
+    // each walk ends at a turn, more turns than (cell, direction) pairs is a loop
+    let mut walks = 0;
+    while !state.done {
+        lab.walk_a(&mut state);
+        walks += 1;
+        if walks > 4 * lab.map.rows * lab.map.cols {
+            return Err(AocError::new(6, "the guard never leaves the lab"));
+        }
     }
 
     // clean up the results
-    state.visits.push((state.cur_row, state.cur_col));
+    state.visits.push(state.pos);
     state.visits.sort_unstable();
     state.visits.dedup();
 
     let s = format!("day_06_a = {}", state.visits.len());
-    (s, state.visits)
+    Ok((s, state.visits))
+}
+
+pub fn day_06_b(lab: &Lab, candidates: &[Point]) -> String {
+    let cnt = loop_obstructions(lab, candidates).len();
+    format!("day_06_b = {}", cnt)
 }
 
-fn day_06_b(grid: &Grid, candidates: &[(usize, usize)]) -> String {
+/// The candidate cells where a new obstruction traps the guard in a loop.
+pub fn loop_obstructions(lab: &Lab, candidates: &[Point]) -> Vec<Point> {
     let state = State {
-        cur_row: grid.start_row,
-        cur_col: grid.start_col,
-        dir: Dir::Up,
+        pos: lab.start,
+        dir: Dir::N,
+        done: false,
         visits: Vec::new(),
     };
 
-    let cnt: usize = candidates
+    // the guard's starting position can not be obstructed
+    candidates
         .par_iter()
-        .map(|&(r, c)| {
+        .filter(|&&p| p != lab.start)
+        .filter(|&&obstacle| {
             let mut tor_state = state.clone();
             let mut hare_state = state.clone();
 
             loop {
-                grid.walk_b(&mut tor_state, r, c);
-                grid.walk_b(&mut hare_state, r, c);
-                grid.walk_b(&mut hare_state, r, c);
-                if hare_state.dir == Dir::Done {
-                    return 0;
-                } else if tor_state.dir == hare_state.dir
-                    && tor_state.cur_row == hare_state.cur_row
-                    && tor_state.cur_col == hare_state.cur_col
-                {
-                    return 1;
+                lab.walk_b(&mut tor_state, obstacle);
+                lab.walk_b(&mut hare_state, obstacle);
+                lab.walk_b(&mut hare_state, obstacle);
+                if hare_state.done {
+                    return false;
+                } else if tor_state.dir == hare_state.dir && tor_state.pos == hare_state.pos {
+                    return true;
                 }
             }
         })
-        .sum();
-
-    format!("day_06_b = {}", cnt)
+        .copied()
+        .collect()
 }
 
-fn parse(s: &str) -> Grid {
-    let mut start_row = 0;
-    let mut start_col = 0;
+/// Draws the guard's path, `O` where an obstruction would trap the guard in a loop.
+pub fn visualize(input: &str) -> Result<Canvas> {
+    let lab = parse(input).map_err(|e| e.in_day(6))?;
+    let (_, visits) = day_06_a(&lab)?;
 
-    // fill grid
-    let mut xss: Vec<Vec<char>> = Vec::new();
-    for (row, line) in s.lines().enumerate() {
-        let mut xs: Vec<char> = line.chars().collect();
-        // pad col
-        xs.insert(0, '_');
-        xs.push('_');
-
-        if let Some(col) = xs.iter().position(|&c| c == '^') {
-            start_row = row + 1;
-            start_col = col;
-        }
-        xss.push(xs);
+    let mut canvas = Canvas::new(lab.map.rows, lab.map.cols);
+    for (p, &ch) in lab.map.iter() {
+        canvas.set(p.row, p.col, ch, if ch == '#' { WALL } else { DIM });
     }
+    for p in &visits {
+        canvas.set(p.row, p.col, 'X', PATH);
+    }
+    for p in loop_obstructions(&lab, &visits) {
+        canvas.set(p.row, p.col, 'O', MARK);
+    }
+    canvas.set(lab.start.row, lab.start.col, '^', START);
+    Ok(canvas)
+}
 
-    // finish padding the grid
-    let cols = xss[0].len();
-    let ts: Vec<char> = std::iter::repeat_n('_', cols).collect();
-    xss.insert(0, ts.clone());
-    xss.push(ts);
-
-    let rows = xss.len();
-    Grid {
-        css: xss,
-        start_row,
-        start_col,
-        rows,
-        cols,
+pub fn parse(s: &str) -> ParseResult<Lab> {
+    let map: Grid<char> = s.parse()?;
+    let mut start = None;
+    for (p, &c) in map.iter() {
+        match c {
+            '.' | '#' => (),
+            '^' if start.is_none() => start = Some(p),
+            '^' => return Err(ParseError::new(p.row + 1, p.col + 1, "more than one guard")),
+            _ => {
+                let msg = format!("unexpected character '{}'", c);
+                return Err(ParseError::new(p.row + 1, p.col + 1, msg));
+            }
+        }
     }
+    let Some(start) = start else {
+        return Err(ParseError::new(1, 1, "no guard '^' in the lab"));
+    };
+    Ok(Lab { map, start })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+    use std::collections::HashSet;
 
-    static STR: &str = r"....#.....
-.........#
-..........
-..#.......
-.......#..
-..........
-.#..^.....
-........#.
-#.........
-......#...";
+    // step one cell at a time, a loop is a repeated (row, col, direction)
+    fn is_loop(css: &[Vec<char>], start: (i32, i32)) -> bool {
+        let dirs = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+        let (rows, cols) = (css.len() as i32, css[0].len() as i32);
+        let (mut r, mut c, mut d) = (start.0, start.1, 0);
+        let mut seen = HashSet::new();
+        loop {
+            if !seen.insert((r, c, d)) {
+                return true;
+            }
+            let (nr, nc) = (r + dirs[d].0, c + dirs[d].1);
+            if nr < 0 || nr >= rows || nc < 0 || nc >= cols {
+                return false;
+            }
+            if css[nr as usize][nc as usize] == '#' {
+                d = (d + 1) % 4;
+            } else {
+                (r, c) = (nr, nc);
+            }
+        }
+    }
 
-    #[test]
-    fn a_test() {
-        let grid = parse(STR);
-        grid.show();
-        let (result, _) = day_06_a(&grid);
-        println!("{}", &result);
-        assert_eq!("day_06_a = 41".to_string(), result);
+    fn grid_and_start(s: &str) -> (Vec<Vec<char>>, (i32, i32)) {
+        let css: Vec<Vec<char>> = s.lines().map(|l| l.chars().collect()).collect();
+        let mut start = (0, 0);
+        for (r, cs) in css.iter().enumerate() {
+            if let Some(c) = cs.iter().position(|&c| c == '^') {
+                start = (r as i32, c as i32);
+            }
+        }
+        (css, start)
     }
 
-    #[test]
-    fn b_test() {
-        let grid = parse(STR);
-        let (_, visits) = day_06_a(&grid);
-        let result = day_06_b(&grid, &visits);
-        println!("{}", &result);
-        assert_eq!("day_06_b = 6".to_string(), result);
+    // place an obstacle on every open cell except the guard's start
+    fn count_loops_naive(s: &str) -> usize {
+        let (css, start) = grid_and_start(s);
+        let mut cnt = 0;
+        for r in 0..css.len() {
+            for c in 0..css[0].len() {
+                if css[r][c] == '.' {
+                    let mut blocked = css.clone();
+                    blocked[r][c] = '#';
+                    cnt += is_loop(&blocked, start) as usize;
+                }
+            }
+        }
+        cnt
+    }
+
+    fn lab() -> impl Strategy<Value = String> {
+        (2usize..10, 2usize..10).prop_flat_map(|(rows, cols)| {
+            let cells = prop::collection::vec(prop::bool::weighted(0.2), rows * cols);
+            (cells, 0..rows * cols).prop_map(move |(walls, start)| {
+                let cs: Vec<char> = walls
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &wall)| match (i == start, wall) {
+                        (true, _) => '^',
+                        (false, true) => '#',
+                        (false, false) => '.',
+                    })
+                    .collect();
+                cs.chunks(cols)
+                    .map(|row| row.iter().collect::<String>())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn day_06_b_oracle_test(s in lab()) {
+            // puzzle inputs always let the guard leave the lab
+            let (css, start) = grid_and_start(&s);
+            prop_assume!(!is_loop(&css, start));
+            let expected = format!("day_06_b = {}", count_loops_naive(&s));
+            prop_assert_eq!(Ok(expected), part_b(&s));
+        }
     }
 }
@@ -1,32 +1,45 @@
-use crate::common::Solution;
+use crate::common::{Solution, Timings};
+use crate::error::{column, parse_number, ParseError, ParseResult, Result};
+use crate::input::load_input;
 use rayon::prelude::*;
 use std::time::Instant;
 
-static INPUT: &str = include_str!("../data/day_07.txt");
-
 #[derive(Clone, Debug)]
 pub struct Op {
     pub desired: usize,
     pub values: Vec<usize>,
 }
 
-pub fn solve_day_07() -> Solution {
+pub fn solve_day_07() -> Result<Solution> {
+    let input = load_input(7)?;
     let start = Instant::now();
-    let ops = parse(INPUT);
+    let ops = parse(&input).map_err(|e| e.in_day(7))?;
+    let parsed = start.elapsed();
     let part_a = day_07_a(&ops); // 5837374519342
+    let solved_a = start.elapsed();
     let part_b = day_07_b(&ops); // 492383931650959
     let duration = start.elapsed();
-    Solution {
+    Ok(Solution {
         part_a,
         part_b,
         duration,
-    }
+        timings: Timings::from_marks(parsed, solved_a, duration),
+    })
+}
+
+pub fn part_a(input: &str) -> Result<String> {
+    Ok(day_07_a(&parse(input).map_err(|e| e.in_day(7))?))
 }
 
-fn day_07_a(ops: &[Op]) -> String {
-    let v: usize = ops.iter().fold(0usize, |acc, op| {
+pub fn part_b(input: &str) -> Result<String> {
+    Ok(day_07_b(&parse(input).map_err(|e| e.in_day(7))?))
+}
+
+// totals in u128, a sum of usize targets can't overflow it
+pub fn day_07_a(ops: &[Op]) -> String {
+    let v: u128 = ops.iter().fold(0u128, |acc, op| {
         if eval_rtl_a(op.desired, op.values.clone()) {
-            op.desired + acc
+            op.desired as u128 + acc
         } else {
             acc
         }
@@ -35,14 +48,14 @@ fn day_07_a(ops: &[Op]) -> String {
     format!("day_07_a = {}", v)
 }
 
-fn day_07_b(ops: &[Op]) -> String {
-    let v: usize = ops
+pub fn day_07_b(ops: &[Op]) -> String {
+    let v: u128 = ops
         .par_iter()
         .fold(
-            || 0usize,
+            || 0u128,
             |acc, op| {
                 if eval_rtl_b(op.desired, op.values.clone()) {
-                    op.desired + acc
+                    op.desired as u128 + acc
                 } else {
                     acc
                 }
@@ -54,14 +67,14 @@ fn day_07_b(ops: &[Op]) -> String {
 }
 
 pub fn eval_rtl_a(desired: usize, values: Vec<usize>) -> bool {
-    match values.len() {
-        0 | 1 => false,
-        2 => values[0] + values[1] == desired || values[0] * values[1] == desired,
-        _ => {
-            let mut new_values = values.clone();
-            let value = new_values.pop().unwrap();
-
-            let is_mul = desired % value == 0 && eval_rtl_a(desired / value, new_values.clone());
+    match values[..] {
+        [] | [_] => false,
+        [a, b] => a.checked_add(b) == Some(desired) || a.checked_mul(b) == Some(desired),
+        [ref rest @ .., value] => {
+            let new_values = rest.to_vec();
+
+            let is_mul =
+                desired.is_multiple_of(value) && eval_rtl_a(desired / value, new_values.clone());
             let is_add = desired >= value && eval_rtl_a(desired - value, new_values);
             is_mul || is_add
         }
@@ -69,19 +82,19 @@ pub fn eval_rtl_a(desired: usize, values: Vec<usize>) -> bool {
 }
 
 pub fn eval_rtl_b(desired: usize, values: Vec<usize>) -> bool {
-    match values.len() {
-        0 => false,
-        1 => values[0] == desired,
-        2 => {
-            values[0] + values[1] == desired
-                || values[0] * values[1] == desired
-                || concat_usize(values[0], values[1]) == desired
+    match values[..] {
+        [] => false,
+        [a] => a == desired,
+        [a, b] => {
+            a.checked_add(b) == Some(desired)
+                || a.checked_mul(b) == Some(desired)
+                || concat_usize(a, b) == Some(desired)
         }
-        _ => {
-            let mut new_values = values.clone();
-            let value = new_values.pop().unwrap();
+        [ref rest @ .., value] => {
+            let new_values = rest.to_vec();
 
-            let is_mul = desired % value == 0 && eval_rtl_b(desired / value, new_values.clone());
+            let is_mul =
+                desired.is_multiple_of(value) && eval_rtl_b(desired / value, new_values.clone());
             let is_add = desired >= value && eval_rtl_b(desired - value, new_values.clone());
             let mut is_concat = false;
             if let Some(lhs) = un_concat_usize(desired, value) {
@@ -92,21 +105,22 @@ pub fn eval_rtl_b(desired: usize, values: Vec<usize>) -> bool {
     }
 }
 
-fn concat_usize(a: usize, b: usize) -> usize {
-    let mut base = 1;
+// none when the digits don't fit, which no target can match then
+fn concat_usize(a: usize, b: usize) -> Option<usize> {
+    let mut base: usize = 1;
     let mut t: usize = b;
     while t > 0 {
-        base *= 10;
+        base = base.checked_mul(10)?;
         t /= 10;
     }
-    a * base + b
+    a.checked_mul(base)?.checked_add(b)
 }
 
 fn un_concat_usize(joined: usize, right_side: usize) -> Option<usize> {
-    let mut divisor = 10;
+    let mut divisor: usize = 10;
     let mut check = right_side;
     while check >= 10 {
-        divisor *= 10;
+        divisor = divisor.checked_mul(10)?;
         check /= 10;
     }
 
@@ -119,48 +133,101 @@ fn un_concat_usize(joined: usize, right_side: usize) -> Option<usize> {
     Some(joined / divisor)
 }
 
-fn parse(input: &str) -> Vec<Op> {
+pub fn parse(input: &str) -> ParseResult<Vec<Op>> {
     let mut evals: Vec<Op> = Vec::new();
-    for line in input.lines() {
-        let parts: Vec<&str> = line.split(":").collect();
-        let desired: usize = parts[0].parse().unwrap();
-        let s = parts[1].trim();
-        let values: Vec<usize> = s.split(" ").map(|x| x.parse().unwrap()).collect();
+    for (i, line) in input.lines().enumerate().filter(|(_, s)| !s.is_empty()) {
+        let Some((lhs, rhs)) = line.split_once(':') else {
+            return Err(ParseError::new(i + 1, 1, "expected 'value: numbers'"));
+        };
+        let desired: usize = parse_number(i + 1, line, lhs)?;
+        let mut values: Vec<usize> = Vec::new();
+        for x in rhs.split(' ').filter(|x| !x.is_empty()) {
+            let value = parse_number(i + 1, line, x)?;
+            if value == 0 {
+                return Err(ParseError::new(
+                    i + 1,
+                    column(line, x),
+                    "values must be positive",
+                ));
+            }
+            values.push(value);
+        }
+        if values.is_empty() {
+            return Err(ParseError::new(i + 1, column(line, rhs), "no values"));
+        }
         evals.push(Op { desired, values });
     }
 
-    evals
+    Ok(evals)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::perms::Perms;
+    use proptest::prelude::*;
 
-    static STR: &str = r"190: 10 19
-3267: 81 40 27
-83: 17 5
-156: 15 6
-7290: 6 8 6 15
-161011: 16 10 13
-192: 17 8 14
-21037: 9 7 18 13
-292: 11 6 16 20";
+    #[derive(Clone, Copy, Debug)]
+    enum Operator {
+        Add,
+        Mul,
+        Concat,
+    }
 
-    #[test]
-    fn a_test() {
-        let ops = parse(STR);
-        let result = day_07_a(&ops);
-        println!("{}", &result);
-        assert_eq!("day_07_a = 3749".to_string(), result);
+    fn eval_ltr(values: &[usize], ops: &[Operator]) -> usize {
+        values[1..]
+            .iter()
+            .zip(ops)
+            .fold(values[0], |acc, (&v, op)| match op {
+                Operator::Add => acc + v,
+                Operator::Mul => acc * v,
+                Operator::Concat => concat_usize(acc, v).unwrap(),
+            })
     }
 
-    #[test]
-    fn b_test() {
-        let ops = parse(STR);
-        let result = day_07_b(&ops);
-        println!("{}", &result);
-        assert_eq!("day_07_b = 11387".to_string(), result);
+    // brute force, try every operator combination left to right
+    fn oracle(desired: usize, values: &[usize], operators: Vec<Operator>) -> bool {
+        Perms::new(values.len() - 1, operators).any(|ops| eval_ltr(values, &ops) == desired)
+    }
+
+    fn equation() -> impl Strategy<Value = (usize, Vec<usize>)> {
+        prop::collection::vec(1usize..100, 2..7).prop_flat_map(|values| {
+            let n = values.len() - 1;
+            let ops = prop::collection::vec(
+                prop_oneof![
+                    Just(Operator::Add),
+                    Just(Operator::Mul),
+                    Just(Operator::Concat)
+                ],
+                n,
+            );
+            // half the time a random target, otherwise one that some operators reach
+            (Just(values), ops, any::<bool>(), 1usize..1_000_000).prop_map(
+                |(values, ops, reachable, random)| {
+                    let desired = if reachable {
+                        eval_ltr(&values, &ops)
+                    } else {
+                        random
+                    };
+                    (desired, values)
+                },
+            )
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn eval_rtl_a_oracle_test((desired, values) in equation()) {
+            let expected = oracle(desired, &values, vec![Operator::Add, Operator::Mul]);
+            prop_assert_eq!(expected, eval_rtl_a(desired, values));
+        }
+
+        #[test]
+        fn eval_rtl_b_oracle_test((desired, values) in equation()) {
+            let operators = vec![Operator::Add, Operator::Mul, Operator::Concat];
+            let expected = oracle(desired, &values, operators);
+            prop_assert_eq!(expected, eval_rtl_b(desired, values));
+        }
     }
 
     #[test]
@@ -194,6 +261,28 @@ mod tests {
         assert!(!result);
     }
 
+    #[test]
+    fn overflow_test() {
+        // overflowing operators match no target
+        let big = usize::MAX / 2 + 1;
+        assert!(!eval_rtl_a(0, vec![big, 2]));
+        assert!(!eval_rtl_b(0, vec![big, big]));
+        assert!(eval_rtl_b(usize::MAX, vec![big, big - 1]));
+    }
+
+    #[test]
+    fn large_total_test() {
+        let big = usize::MAX / 2 + 1;
+        let op = Op {
+            desired: big,
+            values: vec![big - 1, 1],
+        };
+        let ops = vec![op.clone(), op.clone(), op];
+        let total = 3 * big as u128;
+        assert_eq!(format!("day_07_a = {}", total), day_07_a(&ops));
+        assert_eq!(format!("day_07_b = {}", total), day_07_b(&ops));
+    }
+
     #[test]
     fn perms_char_test() {
         let perms = Perms::new(2, vec!['a', 'b']);
@@ -205,9 +294,11 @@ mod tests {
     #[test]
     fn concat_usize_test() {
         let v = concat_usize(15, 6);
-        assert_eq!(v, 156);
+        assert_eq!(v, Some(156));
         let v = concat_usize(1234, 5678);
-        assert_eq!(v, 12345678);
+        assert_eq!(v, Some(12345678));
+        assert_eq!(None, concat_usize(usize::MAX / 10, 10));
+        assert_eq!(None, un_concat_usize(usize::MAX, usize::MAX));
 
         let lhs = un_concat_usize(12345678, 5678);
         assert!(lhs.is_some());
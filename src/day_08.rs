@@ -4,91 +4,88 @@ use std::time::Instant;
 
 use nohash_hasher::NoHashHasher;
 
-use crate::common::Solution;
+use crate::common::{Grid, Point, Solution, Timings};
+use crate::error::{ParseResult, Result};
+use crate::input::load_input;
+use crate::viz::{palette, Canvas, DIM, MARK, MARK_DIM};
 
 #[derive(Clone, Debug)]
-struct Grid {
+pub struct City {
+    pub map: Grid<char>,
     pub antenna_m: FastMap,
-    pub rows: usize,
-    pub cols: usize,
 }
 
-// hashmap<antenna, (row, col)>
-type FastMap = HashMap<char, Vec<(i32, i32)>, BuildHasherDefault<NoHashHasher<usize>>>;
+// hashmap<antenna, positions>
+type FastMap = HashMap<char, Vec<Point>, BuildHasherDefault<NoHashHasher<usize>>>;
 
-static INPUT: &str = include_str!("../data/day_08.txt");
-
-pub fn solve_day_08() -> Solution {
+pub fn solve_day_08() -> Result<Solution> {
+    let input = load_input(8)?;
     let start = Instant::now();
-    let grid = parse(INPUT);
-    let part_a = day_08_a(&grid); // 396
-    let part_b = day_08_b(&grid); // 1196 too low s/b 1200
+    let city = parse(&input).map_err(|e| e.in_day(8))?;
+    let parsed = start.elapsed();
+    let part_a = day_08_a(&city); // 396
+    let solved_a = start.elapsed();
+    let part_b = day_08_b(&city); // 1196 too low s/b 1200
     let duration = start.elapsed();
-    Solution {
+    Ok(Solution {
         part_a,
         part_b,
         duration,
-    }
+        timings: Timings::from_marks(parsed, solved_a, duration),
+    })
 }
 
-fn day_08_a(grid: &Grid) -> String {
-    let mut anti_s: HashSet<(i32, i32)> = HashSet::new();
-    for c in grid.antenna_m.keys() {
-        grid.find_antis(c, &mut anti_s, false);
-    }
+pub fn part_a(input: &str) -> Result<String> {
+    Ok(day_08_a(&parse(input).map_err(|e| e.in_day(8))?))
+}
 
-    let v = anti_s.len();
-    format!("day_08_a = {}", v)
+pub fn part_b(input: &str) -> Result<String> {
+    Ok(day_08_b(&parse(input).map_err(|e| e.in_day(8))?))
 }
 
-fn day_08_b(grid: &Grid) -> String {
-    let mut anti_s: HashSet<(i32, i32)> = HashSet::new();
-    for c in grid.antenna_m.keys() {
-        grid.find_antis(c, &mut anti_s, true);
-    }
+pub fn day_08_a(city: &City) -> String {
+    let v = city.antinodes(false).len();
+    format!("day_08_a = {}", v)
+}
 
-    let v = anti_s.len();
+pub fn day_08_b(city: &City) -> String {
+    let v = city.antinodes(true).len();
     format!("day_08_b = {}", v)
 }
 
-impl Grid {
-    fn find_antis(&self, ch: &char, anti_s: &mut HashSet<(i32, i32)>, many: bool) {
-        let rows: i32 = self.rows as i32;
-        let cols: i32 = self.cols as i32;
-
-        let is_valid = |(r, c): (i32, i32)| -> bool { r >= 0 && r < rows && c >= 0 && c < cols };
+impl City {
+    /// Antinodes of every frequency, `many` for the resonant harmonics of part b.
+    pub fn antinodes(&self, many: bool) -> HashSet<Point> {
+        let mut anti_s: HashSet<Point> = HashSet::new();
+        for c in self.antenna_m.keys() {
+            self.find_antis(c, &mut anti_s, many);
+        }
+        anti_s
+    }
 
-        if self.antenna_m.contains_key(ch) {
-            let ps = self.antenna_m.get(ch).unwrap();
-            for (r1, c1) in ps {
-                for (r2, c2) in ps {
-                    if r1 == r2 && c1 == c2 {
+    fn find_antis(&self, ch: &char, anti_s: &mut HashSet<Point>, many: bool) {
+        if let Some(ps) = self.antenna_m.get(ch) {
+            for &p1 in ps {
+                for &p2 in ps {
+                    if p1 == p2 {
                         continue;
                     }
 
-                    let dr = r1 - r2;
-                    let dc = c1 - c2;
+                    let d = p1 - p2;
 
-                    let anti = (r1 + dr, c1 + dc);
-                    if is_valid(anti) {
+                    if let Some(anti) = self.map.step(p1, d) {
                         anti_s.insert(anti);
                     }
 
                     if many {
                         // add the source points
-                        anti_s.insert((*r1, *c1));
-                        anti_s.insert((*r2, *c2));
-
-                        let (mut new_dr, mut new_dc) = (dr, dc);
-                        loop {
-                            let anti = (r1 + new_dr, c1 + new_dc);
-                            if is_valid(anti) {
-                                anti_s.insert(anti);
-                                new_dr += dr;
-                                new_dc += dc;
-                            } else {
-                                break;
-                            }
+                        anti_s.insert(p1);
+                        anti_s.insert(p2);
+
+                        let mut anti = p1;
+                        while let Some(p) = self.map.step(anti, d) {
+                            anti_s.insert(p);
+                            anti = p;
                         }
                     }
                 }
@@ -97,121 +94,64 @@ impl Grid {
     }
 }
 
-fn parse(s: &str) -> Grid {
-    let mut antenna_m: FastMap =
-        HashMap::with_capacity_and_hasher(100, BuildHasherDefault::default());
-    let mut rows = 0;
-    let mut cols = 0;
-
-    for (row, line) in s.lines().enumerate() {
-        let cs: Vec<char> = line.chars().collect();
-        for (col, c) in cs.iter().enumerate() {
-            if *c != '.' {
-                if antenna_m.contains_key(c) {
-                    antenna_m.get_mut(c).unwrap().push((row as i32, col as i32));
-                } else {
-                    antenna_m.insert(*c, vec![(row as i32, col as i32)]);
-                }
-            }
-            cols = cols.max(col);
+/// Draws the antennas over their antinodes, `#` for part a and `+` for the extra ones of part b.
+pub fn visualize(input: &str) -> Result<Canvas> {
+    let city = parse(input).map_err(|e| e.in_day(8))?;
+    let mut canvas = Canvas::new(city.map.rows, city.map.cols);
+    for p in city.map.positions() {
+        canvas.set(p.row, p.col, '.', DIM);
+    }
+    for p in city.antinodes(true) {
+        canvas.set(p.row, p.col, '+', MARK_DIM);
+    }
+    for p in city.antinodes(false) {
+        canvas.set(p.row, p.col, '#', MARK);
+    }
+    for (&ch, ps) in &city.antenna_m {
+        for p in ps {
+            canvas.set(p.row, p.col, ch, palette(ch as usize));
         }
-        rows = rows.max(row);
     }
+    Ok(canvas)
+}
 
-    rows += 1;
-    cols += 1;
+pub fn parse(s: &str) -> ParseResult<City> {
+    let map: Grid<char> = s.parse()?;
+    let mut antenna_m: FastMap =
+        HashMap::with_capacity_and_hasher(100, BuildHasherDefault::default());
 
-    Grid {
-        antenna_m,
-        rows,
-        cols,
+    for (p, &c) in map.iter() {
+        if c != '.' {
+            antenna_m.entry(c).or_default().push(p);
+        }
     }
+
+    Ok(City { map, antenna_m })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    static STR1: &str = r"..........
-..........
-..........
-....a.....
-........a.
-.....a....
-..........
-..........
-..........
-..........";
-
-    static STR2: &str = r"............
-........0...
-.....0......
-.......0....
-....0.......
-......A.....
-............
-............
-........A...
-.........A..
-............
-............";
-
-    static STR3: &str = r"T.........
-...T......
-.T........
-..........
-..........
-..........
-..........
-..........
-..........
-..........";
+    use crate::input::input_available;
 
     #[test]
     fn parse_test() {
-        let grid = parse(STR1);
-        println!("{:?}", &grid);
-
-        let grid = parse(STR2);
-        println!("{:?}", &grid);
-
-        let grid = parse(INPUT);
-        println!("{:?}", &grid);
-    }
-
-    #[test]
-    fn a_test() {
-        let grid = parse(STR1);
-        let result = day_08_a(&grid);
-        println!("{}", &result);
-        assert_eq!("day_08_a = 4".to_string(), result);
-
-        let grid = parse(STR2);
-        let result = day_08_a(&grid);
-        println!("{}", &result);
-        assert_eq!("day_08_a = 14".to_string(), result);
-
-        let grid = parse(INPUT);
-        let result = day_08_a(&grid);
-        println!("{}", &result);
-        assert_eq!("day_08_a = 396".to_string(), result);
+        if !input_available(8) {
+            eprintln!("skipped, day 8 input is encrypted and no key is configured");
+            return;
+        }
+        let city = parse(&load_input(8).unwrap()).unwrap();
+        assert_eq!((50, 50), (city.map.rows, city.map.cols));
     }
 
     #[test]
-    fn b_test() {
-        let grid = parse(STR3);
-        let result = day_08_b(&grid);
-        println!("{}", &result);
-        assert_eq!("day_08_b = 9".to_string(), result);
-
-        let grid = parse(STR2);
-        let result = day_08_b(&grid);
-        println!("{}", &result);
-        assert_eq!("day_08_b = 34".to_string(), result);
-
-        let grid = parse(INPUT);
-        let result = day_08_b(&grid);
-        println!("{}", &result);
-        assert_eq!("day_08_b = 1200".to_string(), result);
+    fn input_test() {
+        if !input_available(8) {
+            eprintln!("skipped, day 8 input is encrypted and no key is configured");
+            return;
+        }
+        let city = parse(&load_input(8).unwrap()).unwrap();
+        assert_eq!("day_08_a = 396".to_string(), day_08_a(&city));
+        assert_eq!("day_08_b = 1200".to_string(), day_08_b(&city));
     }
 }
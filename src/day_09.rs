@@ -1,4 +1,7 @@
-use crate::common::Solution;
+use crate::common::{Solution, Timings};
+use crate::error::{ParseError, ParseResult, Result};
+use crate::input::load_input;
+use crate::viz::{palette, Canvas, DIM};
 use nohash_hasher::NoHashHasher;
 use std::collections::HashMap;
 use std::hash::BuildHasherDefault;
@@ -6,38 +9,62 @@ use std::time::Instant;
 
 type FastMap = HashMap<usize, (usize, usize), BuildHasherDefault<NoHashHasher<usize>>>;
 
-static INPUT: &str = include_str!("../data/day_09.txt");
-pub fn solve_day_09() -> Solution {
+pub fn solve_day_09() -> Result<Solution> {
+    let input = load_input(9)?;
     let start = Instant::now();
-    let xs = make_block(INPUT);
+    let xs = parse(&input).map_err(|e| e.in_day(9))?;
+    let parsed = start.elapsed();
     let part_a = day_09_a(&xs); // 6310675819476
+    let solved_a = start.elapsed();
     let part_b = day_09_b(&xs); // 6335972980679
     let duration = start.elapsed();
-    Solution {
+    Ok(Solution {
         part_a,
         part_b,
         duration,
-    }
+        timings: Timings::from_marks(parsed, solved_a, duration),
+    })
+}
+
+pub fn part_a(input: &str) -> Result<String> {
+    Ok(day_09_a(&parse(input).map_err(|e| e.in_day(9))?))
 }
 
-fn make_block(s: &str) -> Vec<usize> {
+pub fn part_b(input: &str) -> Result<String> {
+    Ok(day_09_b(&parse(input).map_err(|e| e.in_day(9))?))
+}
+
+pub fn parse(s: &str) -> ParseResult<Vec<usize>> {
+    let s = s.trim_end();
+    if s.is_empty() {
+        return Err(ParseError::new(1, 1, "empty disk map"));
+    }
+    let mut ds = Vec::with_capacity(s.len());
+    for (col, c) in s.chars().enumerate() {
+        match c.to_digit(10) {
+            Some(d) => ds.push(d as usize),
+            None => {
+                let msg = format!("expected a digit, found '{}'", c);
+                return Err(ParseError::new(1, col + 1, msg));
+            }
+        }
+    }
+    // free spots are stored as 1_000_001 + id
+    if ds.len() / 2 >= 1_000_000 {
+        return Err(ParseError::new(1, 1, "more than 1_000_000 files"));
+    }
+
     let mut xs = Vec::new();
-    let mut it_free = s.chars().skip(1).step_by(2);
-    for (id, file) in s.chars().step_by(2).enumerate() {
+    for (id, pair) in ds.chunks(2).enumerate() {
         // write file ids
-        for _ in 0..file.to_digit(10).unwrap() {
-            xs.push(id);
-        }
+        xs.extend(std::iter::repeat_n(id, pair[0]));
 
         // write free spots
-        if let Some(free) = it_free.next() {
-            for _ in 0..free.to_digit(10).unwrap() {
-                xs.push(1_000_001usize + id);
-            }
+        if let Some(&free) = pair.get(1) {
+            xs.extend(std::iter::repeat_n(1_000_001usize + id, free));
         }
     }
-    // println!("{:?}", &xs);
-    xs
+    Ok(xs)
 }
 
 fn split_block(xs: &[usize]) -> (FastMap, Vec<(usize, usize)>) {
@@ -110,12 +137,11 @@ fn find_next_free_idx(
     None
 }
 
-fn day_09_a(_xs: &[usize]) -> String {
-    let mut it_l = _xs.iter().enumerate();
+pub fn day_09_a(_xs: &[usize]) -> String {
+    let it_l = _xs.iter().enumerate();
     let mut it_r = _xs.iter().enumerate().rev();
     let mut xs = _xs.to_vec();
-    loop {
-        let (i, cl) = it_l.next().unwrap();
+    for (i, cl) in it_l {
         if *cl > 1_000_000usize {
             for (j, cr) in it_r.by_ref() {
                 if i >= j {
@@ -130,17 +156,24 @@ fn day_09_a(_xs: &[usize]) -> String {
             }
         }
     }
+    // no free space at all
+    format!("day_09_a = {}", calc_checksum(&xs))
+}
+
+pub fn day_09_b(_xs: &[usize]) -> String {
+    let v = calc_checksum(&compact_files(_xs));
+    format!("day_09_b = {}", v)
 }
 
-fn day_09_b(_xs: &[usize]) -> String {
+/// Moves whole files, highest id first, to the leftmost gap that fits them.
+pub fn compact_files(_xs: &[usize]) -> Vec<usize> {
     let (file_m, mut gaps) = split_block(_xs);
-    let xs: &mut [usize] = &mut _xs.to_vec()[..];
+    let mut xs = _xs.to_vec();
 
-    let mut keys: Vec<usize> = file_m.keys().copied().collect();
-    keys.sort_unstable();
+    let mut files: Vec<(usize, (usize, usize))> = file_m.into_iter().collect();
+    files.sort_unstable();
 
-    for file_id in keys.iter().rev() {
-        let (file_idx, file_len) = *file_m.get(file_id).unwrap();
+    for &(_, (file_idx, file_len)) in files.iter().rev() {
         if let Some(gap_idx) = find_next_free_idx(&mut gaps, file_idx, file_len) {
             for n in 0..file_len {
                 unsafe { xs.swap_unchecked(file_idx + n, gap_idx + n) }
@@ -151,19 +184,94 @@ fn day_09_b(_xs: &[usize]) -> String {
         // println!("{:?}", &xs);
     }
 
-    let v = calc_checksum(&xs);
-    format!("day_09_b = {}", v)
+    xs
+}
+
+/// Draws the disk before and after whole file compaction, 100 blocks to a row.
+pub fn visualize(input: &str) -> Result<Canvas> {
+    let before = parse(input).map_err(|e| e.in_day(9))?;
+    let after = compact_files(&before);
+    let cols = before.len().clamp(1, 100);
+    let rows = before.len().div_ceil(cols);
+
+    // a blank row between before and after
+    let mut canvas = Canvas::new(2 * rows + 1, cols);
+    for (i, xs) in [before, after].iter().enumerate() {
+        for (j, &x) in xs.iter().enumerate() {
+            let (row, col) = (i * (rows + 1) + j / cols, j % cols);
+            if x > 1_000_000 {
+                canvas.set(row, col, '.', DIM);
+            } else {
+                let ch = char::from_digit((x % 10) as u32, 10).unwrap();
+                canvas.set(row, col, ch, palette(x));
+            }
+        }
+    }
+    Ok(canvas)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    // naive whole file compaction over a block list, None marks a free block
+    fn compact_files_naive(disk_map: &str) -> usize {
+        let mut blocks: Vec<Option<usize>> = Vec::new();
+        for (i, c) in disk_map.chars().enumerate() {
+            let len = c.to_digit(10).unwrap() as usize;
+            let block = if i % 2 == 0 { Some(i / 2) } else { None };
+            blocks.extend(std::iter::repeat_n(block, len));
+        }
+
+        let max_id = (disk_map.len() - 1) / 2;
+        for id in (0..=max_id).rev() {
+            let start = blocks.iter().position(|&b| b == Some(id)).unwrap();
+            let len = blocks.iter().filter(|&&b| b == Some(id)).count();
+            let mut run = 0;
+            for i in 0..start {
+                run = if blocks[i].is_none() { run + 1 } else { 0 };
+                if run == len {
+                    for j in 0..len {
+                        blocks.swap(i + 1 - len + j, start + j);
+                    }
+                    break;
+                }
+            }
+        }
+
+        blocks
+            .iter()
+            .enumerate()
+            .map(|(i, b)| b.map_or(0, |id| i * id))
+            .sum()
+    }
+
+    fn disk_map() -> impl Strategy<Value = String> {
+        prop::collection::vec((1u32..10, 0u32..10), 1..30).prop_map(|pairs| {
+            let mut s: String = pairs
+                .iter()
+                .flat_map(|&(file, free)| [file, free])
+                .map(|d| char::from_digit(d, 10).unwrap())
+                .collect();
+            s.pop(); // a disk map ends with a file
+            s
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn day_09_b_oracle_test(s in disk_map()) {
+            let expected = format!("day_09_b = {}", compact_files_naive(&s));
+            prop_assert_eq!(expected, day_09_b(&parse(&s).unwrap()));
+        }
+    }
 
     #[test]
-    fn make_block_test() {
+    fn parse_test() {
         let s = "2333133121414131402";
-        let xs = make_block(s);
-        println!("{:?}", &xs);
+        let xs = parse(s).unwrap();
+        println!("{:?}", xs);
         let cs_expected = vec![
             0, 0, 1_000_001, 1_000_001, 1_000_001, 1, 1, 1, 1_000_002, 1_000_002, 1_000_002, 2,
             1_000_003, 1_000_003, 1_000_003, 3, 3, 3, 1_000_004, 4, 4, 1_000_005, 5, 5, 5, 5,
@@ -172,35 +280,17 @@ mod tests {
         assert_eq!(cs_expected, xs);
     }
 
-    #[test]
-    fn a_test() {
-        let s = "2333133121414131402";
-        let xs = make_block(s);
-        let result = day_09_a(&xs);
-        println!("{}", &result);
-        assert_eq!("day_09_a = 1928".to_string(), result);
-    }
-
     #[test]
     fn split_block_test() {
         let s = "2333133121414131402";
-        let xs = make_block(s);
+        let xs = parse(s).unwrap();
         let (m, gaps) = split_block(&xs);
-        println!("{:?}", &m);
-        println!("{:?}", &xs);
+        println!("{:?}", m);
+        println!("{:?}", xs);
         assert!(m.contains_key(&0));
         assert_eq!(Some(&(0, 2)), m.get(&0));
         assert!(m.contains_key(&8));
         assert_eq!(Some(&(36, 4)), m.get(&8));
         assert!(gaps.contains(&(8, 3)));
     }
-
-    #[test]
-    fn b_test() {
-        let s = "2333133121414131402";
-        let xs = make_block(s);
-        let result = day_09_b(&xs);
-        println!("{}", &result);
-        assert_eq!("day_09_b = 2858".to_string(), result);
-    }
 }
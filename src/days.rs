@@ -0,0 +1,87 @@
+use crate::common::Solution;
+use crate::error::Result;
+use crate::viz::Canvas;
+use crate::{day_01, day_02, day_03, day_04, day_05, day_06, day_07, day_08, day_09};
+
+/// Entry points for one AOC day.
+///
+/// `solve` runs both parts against the stored puzzle input, `part_a`/`part_b`
+/// run a single part against any input string (examples, other accounts).
+/// `visualize` draws the puzzle state for the days that have a picture.
+pub struct Day {
+    pub day: u8,
+    pub solve: fn() -> Result<Solution>,
+    pub part_a: fn(&str) -> Result<String>,
+    pub part_b: fn(&str) -> Result<String>,
+    pub visualize: Option<fn(&str) -> Result<Canvas>>,
+}
+
+pub static DAYS: [Day; 9] = [
+    Day {
+        day: 1,
+        solve: day_01::solve_day_01,
+        part_a: day_01::part_a,
+        part_b: day_01::part_b,
+        visualize: None,
+    },
+    Day {
+        day: 2,
+        solve: day_02::solve_day_02,
+        part_a: day_02::part_a,
+        part_b: day_02::part_b,
+        visualize: None,
+    },
+    Day {
+        day: 3,
+        solve: day_03::solve_day_03,
+        part_a: day_03::part_a,
+        part_b: day_03::part_b,
+        visualize: None,
+    },
+    Day {
+        day: 4,
+        solve: day_04::solve_day_04,
+        part_a: day_04::part_a,
+        part_b: day_04::part_b,
+        visualize: Some(day_04::visualize),
+    },
+    Day {
+        day: 5,
+        solve: day_05::solve_day_05,
+        part_a: day_05::part_a,
+        part_b: day_05::part_b,
+        visualize: None,
+    },
+    Day {
+        day: 6,
+        solve: day_06::solve_day_06,
+        part_a: day_06::part_a,
+        part_b: day_06::part_b,
+        visualize: Some(day_06::visualize),
+    },
+    Day {
+        day: 7,
+        solve: day_07::solve_day_07,
+        part_a: day_07::part_a,
+        part_b: day_07::part_b,
+        visualize: None,
+    },
+    Day {
+        day: 8,
+        solve: day_08::solve_day_08,
+        part_a: day_08::part_a,
+        part_b: day_08::part_b,
+        visualize: Some(day_08::visualize),
+    },
+    Day {
+        day: 9,
+        solve: day_09::solve_day_09,
+        part_a: day_09::part_a,
+        part_b: day_09::part_b,
+        visualize: Some(day_09::visualize),
+    },
+];
+
+pub fn get_day(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
@@ -0,0 +1,101 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A problem with puzzle input, lines and columns are 1-based.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+pub type ParseResult<T> = std::result::Result<T, ParseError>;
+
+/// An error while solving a day, with the input position when it came from parsing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AocError {
+    pub day: u8,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+impl AocError {
+    pub fn new(day: u8, message: impl Into<String>) -> Self {
+        AocError {
+            day,
+            line: None,
+            column: None,
+            message: message.into(),
+        }
+    }
+}
+
+impl ParseError {
+    pub fn in_day(self, day: u8) -> AocError {
+        AocError {
+            day,
+            line: Some(self.line),
+            column: Some(self.column),
+            message: self.message,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "day_{:02}", self.day)?;
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, " line {}, column {}", line, column)?,
+            (Some(line), None) => write!(f, " line {}", line)?,
+            _ => (),
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl Error for AocError {}
+
+/// 1-based column of `part`, which must be a sub slice of `text`.
+pub fn column(text: &str, part: &str) -> usize {
+    text[..part.as_ptr() as usize - text.as_ptr() as usize]
+        .chars()
+        .count()
+        + 1
+}
+
+/// Parses `token`, a sub slice of the input line `text`, reporting its position on failure.
+pub fn parse_number<T: FromStr>(line: usize, text: &str, token: &str) -> ParseResult<T> {
+    token.parse().map_err(|_| {
+        ParseError::new(
+            line,
+            column(text, token),
+            format!("invalid number '{}'", token),
+        )
+    })
+}
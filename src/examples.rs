@@ -0,0 +1,201 @@
+//! Example inputs stored as files.
+//!
+//! Each example is a pair of files in `data/examples/day_XX/`: `NAME.txt` holds the
+//! input and `NAME.expected` the answers, one `a: VALUE` / `b: VALUE` line per part.
+//! A part without a line is not checked, as some examples only apply to one part.
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::api::{solve, Part};
+
+#[derive(Clone, Debug)]
+pub struct Example {
+    pub day: u8,
+    pub name: String,
+    pub input: String,
+    pub part_a: Option<String>,
+    pub part_b: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Check {
+    pub day: u8,
+    pub name: String,
+    pub part: char,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = format!("day_{:02} {} part {}", self.day, self.name, self.part);
+        if self.passed() {
+            write!(f, "{:<24} ok   {}", label, self.actual)
+        } else {
+            write!(
+                f,
+                "{:<24} FAIL expected {}, got {}",
+                label, self.expected, self.actual
+            )
+        }
+    }
+}
+
+pub fn examples_dir() -> PathBuf {
+    env::var_os("AOC_EXAMPLES_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/data/examples")))
+}
+
+/// Loads every example below `dir`, ordered by day and name.
+pub fn load_examples(dir: &Path) -> io::Result<Vec<Example>> {
+    let mut examples = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let day = match path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .and_then(|s| s.strip_prefix("day_"))
+            .and_then(|s| s.parse::<u8>().ok())
+        {
+            Some(day) if path.is_dir() => day,
+            _ => continue,
+        };
+        examples.extend(load_day_examples(&path, day)?);
+    }
+    examples.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    Ok(examples)
+}
+
+pub fn load_day_examples(dir: &Path, day: u8) -> io::Result<Vec<Example>> {
+    let mut examples = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = match (path.file_stem(), path.extension()) {
+            (Some(stem), Some(ext)) if ext == "txt" => stem.to_string_lossy().to_string(),
+            _ => continue,
+        };
+        let expected = fs::read_to_string(path.with_extension("expected"))?;
+        let (part_a, part_b) = parse_expected(&expected);
+        examples.push(Example {
+            day,
+            name,
+            input: fs::read_to_string(&path)?,
+            part_a,
+            part_b,
+        });
+    }
+    Ok(examples)
+}
+
+fn parse_expected(s: &str) -> (Option<String>, Option<String>) {
+    let mut part_a = None;
+    let mut part_b = None;
+    for line in s.lines() {
+        match line.split_once(':') {
+            Some(("a", v)) => part_a = Some(v.trim().to_string()),
+            Some(("b", v)) => part_b = Some(v.trim().to_string()),
+            _ => (),
+        }
+    }
+    (part_a, part_b)
+}
+
+/// The first numeric example name not used in `dir`.
+pub fn next_name(dir: &Path) -> String {
+    (1..)
+        .map(|n: usize| n.to_string())
+        .find(|name| !dir.join(format!("{}.txt", name)).exists())
+        .unwrap()
+}
+
+/// Writes `NAME.txt` and `NAME.expected` below `dir/day_XX`, returning the example.
+pub fn write_example(
+    dir: &Path,
+    day: u8,
+    name: &str,
+    input: &str,
+    part_a: Option<&str>,
+    part_b: Option<&str>,
+) -> io::Result<Example> {
+    let dir = dir.join(format!("day_{:02}", day));
+    fs::create_dir_all(&dir)?;
+    let mut expected = String::new();
+    for (part, answer) in [("a", part_a), ("b", part_b)] {
+        if let Some(answer) = answer {
+            expected.push_str(&format!("{}: {}\n", part, answer));
+        }
+    }
+    fs::write(dir.join(format!("{}.txt", name)), input)?;
+    fs::write(dir.join(format!("{}.expected", name)), &expected)?;
+    Ok(Example {
+        day,
+        name: name.to_string(),
+        input: input.to_string(),
+        part_a: part_a.map(str::to_string),
+        part_b: part_b.map(str::to_string),
+    })
+}
+
+/// Runs the parts of `example` that have an expected answer.
+pub fn run_example(example: &Example) -> Vec<Check> {
+    let parts = [(Part::A, &example.part_a), (Part::B, &example.part_b)];
+    parts
+        .into_iter()
+        .filter_map(|(part, expected)| {
+            expected.as_ref().map(|expected| Check {
+                day: example.day,
+                name: example.name.clone(),
+                part: part.as_char(),
+                expected: expected.clone(),
+                actual: match solve(example.day, part, &example.input) {
+                    Ok(answer) => answer.value,
+                    Err(e) => format!("error: {}", e),
+                },
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_example_test() {
+        let dir = std::env::temp_dir().join(format!("aoc_examples_test_{}", std::process::id()));
+        let day_dir = dir.join("day_01");
+        fs::create_dir_all(&day_dir).unwrap();
+        assert_eq!("1", next_name(&day_dir));
+
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+        write_example(&dir, 1, "1", input, Some("11"), None).unwrap();
+        assert_eq!("2", next_name(&day_dir));
+        let examples = load_day_examples(&day_dir, 1).unwrap();
+        assert_eq!(1, examples.len());
+        assert_eq!(
+            (Some("11".to_string()), None),
+            (examples[0].part_a.clone(), examples[0].part_b.clone())
+        );
+        assert!(run_example(&examples[0]).iter().all(Check::passed));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn parse_expected_test() {
+        assert_eq!(
+            (Some("11".to_string()), Some("31".to_string())),
+            parse_expected("a: 11\nb: 31\n")
+        );
+        assert_eq!((None, Some("9".to_string())), parse_expected("b: 9\n"));
+    }
+}
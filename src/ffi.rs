@@ -0,0 +1,151 @@
+//! C interface to the solvers, built into the `cdylib`.
+//!
+//! `include/aoc2024.h` is generated from this module with cbindgen, `cargo test`
+//! fails when it is out of date (`AOC_BLESS=1 cargo test --test ffi` rewrites it).
+use std::os::raw::{c_char, c_int};
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice, str};
+
+use crate::api::{solve, Part};
+use crate::days::get_day;
+
+/// The only year this library solves.
+pub const AOC_YEAR: u32 = 2024;
+
+// status codes returned by `aoc_solve`, on anything but AOC_OK / AOC_ERR_BUFFER
+// the buffer holds an error message instead of the answer
+pub const AOC_OK: c_int = 0;
+/// `input_ptr` or `out_buf` (or its data) is null.
+pub const AOC_ERR_NULL: c_int = 1;
+/// The input is not valid UTF-8.
+pub const AOC_ERR_UTF8: c_int = 2;
+pub const AOC_ERR_YEAR: c_int = 3;
+/// Not a day of December, or a day without a solver yet.
+pub const AOC_ERR_DAY: c_int = 4;
+/// `part` is not 1 or 2.
+pub const AOC_ERR_PART: c_int = 5;
+/// The input could not be parsed, the message holds the line and column.
+pub const AOC_ERR_PARSE: c_int = 6;
+/// The input parsed but has no answer.
+pub const AOC_ERR_SOLVE: c_int = 7;
+/// The buffer is too small, `len` holds the length needed without the NUL.
+pub const AOC_ERR_BUFFER: c_int = 8;
+pub const AOC_ERR_PANIC: c_int = 9;
+
+/// Caller owned output buffer, `aoc_solve` writes a NUL terminated string to
+/// `data` and its length without the NUL to `len`.
+#[repr(C)]
+pub struct AocBuffer {
+    pub data: *mut c_char,
+    pub capacity: usize,
+    pub len: usize,
+}
+
+/// Solves `part` (1 or 2) of `day` for the `len` bytes of input at `input_ptr`,
+/// returns one of the `AOC_` status codes.
+///
+/// # Safety
+///
+/// `input_ptr` must point to `len` readable bytes, `out_buf` to a valid `AocBuffer`
+/// whose `data` points to `capacity` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    year: u32,
+    day: u32,
+    part: u32,
+    input_ptr: *const u8,
+    len: usize,
+    out_buf: *mut AocBuffer,
+) -> c_int {
+    if input_ptr.is_null() || out_buf.is_null() || (*out_buf).data.is_null() {
+        return AOC_ERR_NULL;
+    }
+    let out = &mut *out_buf;
+    let input = slice::from_raw_parts(input_ptr, len);
+    let (status, text) = match panic::catch_unwind(AssertUnwindSafe(|| run(year, day, part, input)))
+    {
+        Ok(result) => result,
+        Err(_) => (AOC_ERR_PANIC, "solver panicked".to_string()),
+    };
+    match write_buffer(out, &text) {
+        Ok(()) => status,
+        Err(e) => e,
+    }
+}
+
+fn run(year: u32, day: u32, part: u32, input: &[u8]) -> (c_int, String) {
+    if year != AOC_YEAR {
+        return (AOC_ERR_YEAR, format!("unsupported year {}", year));
+    }
+    let Some(day) = u8::try_from(day).ok().filter(|d| (1..=25).contains(d)) else {
+        return (AOC_ERR_DAY, format!("unknown day {}", day));
+    };
+    if get_day(day).is_none() {
+        return (AOC_ERR_DAY, format!("no solver for day {}", day));
+    }
+    let part = match part {
+        1 => Part::A,
+        2 => Part::B,
+        _ => return (AOC_ERR_PART, format!("unknown part {}", part)),
+    };
+    let input = match str::from_utf8(input) {
+        Ok(input) => input,
+        Err(e) => return (AOC_ERR_UTF8, e.to_string()),
+    };
+    match solve(day, part, input) {
+        Ok(answer) => (AOC_OK, answer.value),
+        // parse errors carry a position, solve errors don't
+        Err(e) if e.line.is_some() => (AOC_ERR_PARSE, e.to_string()),
+        Err(e) => (AOC_ERR_SOLVE, e.to_string()),
+    }
+}
+
+/// Writes `text` and a NUL to `out`, `Err(AOC_ERR_BUFFER)` when they don't fit.
+/// Also for plugins, which answer through the same buffer.
+///
+/// # Safety
+///
+/// `out.data` must point to `out.capacity` writable bytes.
+pub unsafe fn write_buffer(out: &mut AocBuffer, text: &str) -> Result<(), c_int> {
+    out.len = text.len();
+    if text.len() >= out.capacity {
+        return Err(AOC_ERR_BUFFER);
+    }
+    ptr::copy_nonoverlapping(text.as_ptr(), out.data as *mut u8, text.len());
+    *out.data.add(text.len()) = 0;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(day: u32, part: u32, input: &str, capacity: usize) -> (c_int, String) {
+        let mut data = vec![0 as c_char; capacity.max(1)];
+        let mut out = AocBuffer {
+            data: data.as_mut_ptr(),
+            capacity,
+            len: 0,
+        };
+        let status = unsafe { aoc_solve(2024, day, part, input.as_ptr(), input.len(), &mut out) };
+        let text = data[..out.len.min(capacity)]
+            .iter()
+            .map(|&c| c as u8 as char)
+            .collect();
+        (status, text)
+    }
+
+    #[test]
+    fn aoc_solve_test() {
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+        assert_eq!((AOC_OK, "31".to_string()), call(1, 2, input, 16));
+        assert_eq!(AOC_ERR_BUFFER, call(1, 2, input, 2).0);
+        assert_eq!(AOC_ERR_DAY, call(26, 1, input, 64).0);
+        let unsolved = (AOC_ERR_DAY, "no solver for day 12".to_string());
+        assert_eq!(unsolved, call(12, 1, input, 64));
+        assert_eq!(AOC_ERR_PART, call(1, 3, input, 64).0);
+        let (status, message) = call(1, 1, "3 x\n", 64);
+        assert_eq!(AOC_ERR_PARSE, status);
+        assert_eq!("day_01 line 1, column 3: invalid number 'x'", message);
+    }
+}
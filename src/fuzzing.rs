@@ -0,0 +1,48 @@
+//! Parser entry points for the fuzz targets in `fuzz/`, not a stable API.
+use crate::error::ParseResult;
+use crate::{day_01, day_02, day_04, day_05, day_06, day_07, day_08, day_09};
+
+pub fn parse_day_01(s: &str) -> ParseResult<()> {
+    day_01::parse(s).map(|_| ())
+}
+
+pub fn parse_day_02(s: &str) -> ParseResult<()> {
+    day_02::parse(s).map(|_| ())
+}
+
+pub fn parse_day_04(s: &str) -> ParseResult<()> {
+    day_04::parse(s).map(|_| ())
+}
+
+pub fn parse_day_05(s: &str) -> ParseResult<()> {
+    day_05::parse(s).map(|_| ())
+}
+
+pub fn parse_day_06(s: &str) -> ParseResult<()> {
+    day_06::parse(s).map(|_| ())
+}
+
+pub fn parse_day_07(s: &str) -> ParseResult<()> {
+    day_07::parse(s).map(|_| ())
+}
+
+pub fn parse_day_08(s: &str) -> ParseResult<()> {
+    day_08::parse(s).map(|_| ())
+}
+
+pub fn parse_day_09(s: &str) -> ParseResult<()> {
+    day_09::parse(s).map(|_| ())
+}
+
+pub type Parser = fn(&str) -> ParseResult<()>;
+
+pub static PARSERS: [(&str, Parser); 8] = [
+    ("day_01", parse_day_01),
+    ("day_02", parse_day_02),
+    ("day_04", parse_day_04),
+    ("day_05", parse_day_05),
+    ("day_06", parse_day_06),
+    ("day_07", parse_day_07),
+    ("day_08", parse_day_08),
+    ("day_09", parse_day_09),
+];
@@ -0,0 +1,210 @@
+//! Timings of past runs, for report trends.
+//!
+//! Every run of the solvers appends a line per day to `.aoc_history` in the crate
+//! root (or `AOC_HISTORY_FILE`): the run's start in ms since the epoch, the day and
+//! the parse, part A and part B times in ns, tab separated, then `par` for days run
+//! in parallel. Lines of one run share the timestamp.
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::common::Timings;
+
+pub fn history_file() -> PathBuf {
+    env::var_os("AOC_HISTORY_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/.aoc_history")))
+}
+
+/// The timings of every day in one run.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Run {
+    /// ms since the epoch
+    pub timestamp: u64,
+    pub days: Vec<(u8, Timings)>,
+    /// The days ran in parallel, competing for cores.
+    pub parallel: bool,
+}
+
+impl Run {
+    /// An empty run starting now.
+    pub fn now() -> Run {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as u64);
+        Run {
+            timestamp,
+            days: Vec::new(),
+            parallel: false,
+        }
+    }
+
+    pub fn get(&self, day: u8) -> Option<Timings> {
+        self.days.iter().find(|(d, _)| *d == day).map(|&(_, t)| t)
+    }
+}
+
+/// Runs in file order, a missing file has no runs. Malformed lines are skipped.
+pub fn load(path: &Path) -> io::Result<Vec<Run>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut runs: Vec<Run> = Vec::new();
+    for (timestamp, day, timings, parallel) in text.lines().filter_map(parse_line) {
+        match runs.last_mut() {
+            Some(run) if run.timestamp == timestamp => run.days.push((day, timings)),
+            _ => runs.push(Run {
+                timestamp,
+                days: vec![(day, timings)],
+                parallel,
+            }),
+        }
+    }
+    Ok(runs)
+}
+
+pub fn append(path: &Path, run: &Run) -> io::Result<()> {
+    let lines: String = run
+        .days
+        .iter()
+        .map(|(day, t)| {
+            format!(
+                "{}\t{}\t{}\t{}\t{}{}\n",
+                run.timestamp,
+                day,
+                t.parse.as_nanos(),
+                t.part_a.as_nanos(),
+                t.part_b.as_nanos(),
+                if run.parallel { "\tpar" } else { "" }
+            )
+        })
+        .collect();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(lines.as_bytes())
+}
+
+/// Expected total time of each day, the mean of its last `window` recorded runs.
+pub fn expected_durations(runs: &[Run], window: usize) -> HashMap<u8, Duration> {
+    let mut seen: HashMap<u8, Vec<Duration>> = HashMap::new();
+    for run in runs.iter().rev() {
+        for &(day, timings) in &run.days {
+            let times = seen.entry(day).or_default();
+            if times.len() < window {
+                times.push(timings.total());
+            }
+        }
+    }
+    seen.into_iter()
+        .map(|(day, times)| (day, times.iter().sum::<Duration>() / times.len() as u32))
+        .collect()
+}
+
+/// Indices of `days` in the order to start them: days without history first, as
+/// they may be slow, then the longest expected first.
+pub fn longest_first(days: &[u8], expected: &HashMap<u8, Duration>) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..days.len()).collect();
+    order.sort_by_key(|&i| Reverse(expected.get(&days[i]).copied().unwrap_or(Duration::MAX)));
+    order
+}
+
+// timestamp, day, parse ns, part A ns, part B ns and whether the run was parallel
+fn parse_line(line: &str) -> Option<(u64, u8, Timings, bool)> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    let (fields, parallel) = match fields.split_last() {
+        Some((&"par", rest)) => (rest, true),
+        _ => (&fields[..], false),
+    };
+    let &[timestamp, day, parse, part_a, part_b] = fields else {
+        return None;
+    };
+    let nanos = |s: &str| s.parse().ok().map(Duration::from_nanos);
+    let timings = Timings {
+        parse: nanos(parse)?,
+        part_a: nanos(part_a)?,
+        part_b: nanos(part_b)?,
+    };
+    Some((
+        timestamp.parse().ok()?,
+        day.parse().ok()?,
+        timings,
+        parallel,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_test() {
+        let path = env::temp_dir().join(format!("aoc_history_test_{}", std::process::id()));
+        let timings = |us| Timings {
+            parse: Duration::from_micros(us),
+            part_a: Duration::from_micros(2 * us),
+            part_b: Duration::from_micros(3 * us),
+        };
+        let first = Run {
+            timestamp: 1,
+            days: vec![(1, timings(10)), (2, timings(20))],
+            parallel: false,
+        };
+        let second = Run {
+            timestamp: 2,
+            days: vec![(1, timings(11))],
+            parallel: true,
+        };
+        append(&path, &first).unwrap();
+        append(&path, &second).unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"garbage\n")
+            .unwrap();
+
+        let runs = load(&path).unwrap();
+        assert_eq!(vec![first, second], runs);
+        assert_eq!(
+            Some(Duration::from_micros(66)),
+            runs[1].get(1).map(|t| t.total())
+        );
+        assert_eq!(None, runs[1].get(2));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn longest_first_test() {
+        let run = |timestamp, days: &[(u8, u64)]| Run {
+            timestamp,
+            parallel: false,
+            days: days
+                .iter()
+                .map(|&(day, ms)| {
+                    let timings = Timings {
+                        part_a: Duration::from_millis(ms),
+                        ..Timings::default()
+                    };
+                    (day, timings)
+                })
+                .collect(),
+        };
+        let runs = vec![
+            run(1, &[(1, 100), (2, 1)]),
+            run(2, &[(1, 10), (2, 4)]),
+            run(3, &[(1, 20), (2, 2), (3, 15)]),
+        ];
+        // only the last two runs count
+        let expected = expected_durations(&runs, 2);
+        assert_eq!(Some(&Duration::from_millis(15)), expected.get(&1));
+        assert_eq!(Some(&Duration::from_millis(3)), expected.get(&2));
+        assert_eq!(Some(&Duration::from_millis(15)), expected.get(&3));
+        // day 4 has no history, the stable sort keeps day 1 before day 3
+        assert_eq!(vec![3, 0, 2, 1], longest_first(&[1, 2, 3, 4], &expected));
+    }
+}
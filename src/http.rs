@@ -0,0 +1,51 @@
+//! Plain HTTP GET for the adventofcode.com pages and APIs.
+use std::io;
+
+/// Fetches `url` as text, sending `session` as the session cookie when given.
+pub fn get(url: &str, session: Option<&str>) -> io::Result<String> {
+    let mut request = ureq::get(url).header("User-Agent", "github.com/roy-crippen/advent2024");
+    if let Some(session) = session {
+        request = request.header("Cookie", &format!("session={}", session.trim()));
+    }
+    let mut response = request
+        .call()
+        .map_err(|e| io::Error::other(format!("{}: {}", url, e)))?;
+    response
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| io::Error::other(format!("{}: {}", url, e)))
+}
+
+/// Whether `url` is on the same scheme, host and port as `base_url`, so it may be
+/// sent the session cookie meant for `base_url`.
+pub fn same_origin(url: &str, base_url: &str) -> bool {
+    // scheme://host[:port], without any path
+    let origin = |u: &str| {
+        let (scheme, rest) = u.split_once("://")?;
+        let host = rest.split(['/', '?', '#']).next()?;
+        Some(format!("{}://{}", scheme, host).to_ascii_lowercase())
+    };
+    origin(url).is_some_and(|o| Some(o) == origin(base_url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_origin_test() {
+        let base = "https://adventofcode.com";
+        assert!(same_origin("https://adventofcode.com/2024/x.json", base));
+        assert!(same_origin(
+            "https://AdventOfCode.com",
+            "https://adventofcode.com/"
+        ));
+        assert!(!same_origin("https://evil.example/2024/x.json", base));
+        assert!(!same_origin(
+            "https://adventofcode.com.evil.example/x",
+            base
+        ));
+        assert!(!same_origin("http://adventofcode.com/x", base));
+        assert!(!same_origin("not a url", base));
+    }
+}
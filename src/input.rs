@@ -0,0 +1,212 @@
+//! Puzzle input store.
+//!
+//! Advent of Code asks that inputs are not published, so `data/` may hold
+//! either plain `day_XX.txt` files or `day_XX.txt.enc` files encrypted with
+//! ChaCha20-Poly1305. Solvers call [`load_input`] and never see the difference.
+//!
+//! The 32 byte key is read, as 64 hex characters, from the `AOC_INPUT_KEY`
+//! environment variable or else from the key file named by `AOC_INPUT_KEY_FILE`
+//! (default `.aoc_key` in the crate root). `AOC_DATA_DIR` overrides `data/`.
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+use crate::common::hex;
+use crate::error::{AocError, Result};
+
+const MAGIC: &[u8; 4] = b"AOC1";
+const NONCE_LEN: usize = 12;
+pub const ENC_EXT: &str = "enc";
+
+pub fn data_dir() -> PathBuf {
+    env::var_os("AOC_DATA_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/data")))
+}
+
+pub fn key_file() -> PathBuf {
+    env::var_os("AOC_INPUT_KEY_FILE")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/.aoc_key")))
+}
+
+/// Returns the configured key, `Ok(None)` when neither the variable nor the key file exist.
+pub fn load_key() -> io::Result<Option<[u8; 32]>> {
+    let hex = match env::var("AOC_INPUT_KEY") {
+        Ok(s) => s,
+        Err(_) => match fs::read_to_string(key_file()) {
+            Ok(s) => s,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        },
+    };
+    parse_key(hex.trim()).map(Some)
+}
+
+pub fn parse_key(hex: &str) -> io::Result<[u8; 32]> {
+    let invalid = || invalid_data("input key must be 64 hex characters");
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let mut key = [0u8; 32];
+    for (i, b) in key.iter_mut().enumerate() {
+        *b = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+    }
+    Ok(key)
+}
+
+pub fn format_key(key: &[u8; 32]) -> String {
+    hex(key)
+}
+
+pub fn generate_key() -> [u8; 32] {
+    ChaCha20Poly1305::generate_key(&mut OsRng).into()
+}
+
+pub fn encrypt(key: &[u8; 32], plain: &[u8]) -> Vec<u8> {
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let sealed = cipher
+        .encrypt(&nonce, plain)
+        .expect("chacha20poly1305 encryption is infallible for in-memory buffers");
+
+    let mut out = Vec::with_capacity(MAGIC.len() + NONCE_LEN + sealed.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&sealed);
+    out
+}
+
+pub fn decrypt(key: &[u8; 32], sealed: &[u8]) -> io::Result<Vec<u8>> {
+    if sealed.len() < MAGIC.len() + NONCE_LEN || &sealed[..MAGIC.len()] != MAGIC {
+        return Err(invalid_data("not an encrypted puzzle input"));
+    }
+    let (nonce, body) = sealed[MAGIC.len()..].split_at(NONCE_LEN);
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), body)
+        .map_err(|_| invalid_data("wrong key or corrupted input file"))
+}
+
+/// Reads `data/day_XX.txt`, falling back to decrypting `data/day_XX.txt.enc`.
+pub fn read_input(dir: &Path, day: u8) -> io::Result<String> {
+    let plain = dir.join(format!("day_{:02}.txt", day));
+    if plain.exists() {
+        return fs::read_to_string(plain);
+    }
+
+    let sealed_path = plain.with_extension(format!("txt.{}", ENC_EXT));
+    let sealed = fs::read(&sealed_path)?;
+    let key = load_key()?.ok_or_else(|| {
+        invalid_data(&format!(
+            "{} is encrypted but no key is configured (AOC_INPUT_KEY or {})",
+            sealed_path.display(),
+            key_file().display()
+        ))
+    })?;
+    String::from_utf8(decrypt(&key, &sealed)?).map_err(|_| invalid_data("input is not utf-8"))
+}
+
+/// Whether `day`'s input can be read: it is there in plain text, or encrypted with
+/// a key configured. Tests against the real inputs skip themselves otherwise.
+pub fn input_available(day: u8) -> bool {
+    let plain = data_dir().join(format!("day_{:02}.txt", day));
+    let sealed = plain.with_extension(format!("txt.{}", ENC_EXT));
+    plain.exists() || (sealed.exists() && matches!(load_key(), Ok(Some(_))))
+}
+
+pub fn load_input(day: u8) -> Result<String> {
+    read_input(&data_dir(), day)
+        .map_err(|e| AocError::new(day, format!("unable to load input: {}", e)))
+}
+
+/// Encrypts every `day_XX.txt` in `dir`, returning the written files.
+pub fn encrypt_dir(dir: &Path, key: &[u8; 32], remove_plain: bool) -> io::Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for path in day_files(dir, "txt")? {
+        let sealed_path = path.with_extension(format!("txt.{}", ENC_EXT));
+        fs::write(&sealed_path, encrypt(key, &fs::read(&path)?))?;
+        if remove_plain {
+            fs::remove_file(&path)?;
+        }
+        written.push(sealed_path);
+    }
+    Ok(written)
+}
+
+/// Decrypts every `day_XX.txt.enc` in `dir`, returning the written files.
+pub fn decrypt_dir(dir: &Path, key: &[u8; 32], remove_sealed: bool) -> io::Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for path in day_files(dir, ENC_EXT)? {
+        // day_XX.txt.enc -> day_XX.txt
+        let plain_path = path.with_extension("");
+        fs::write(&plain_path, decrypt(key, &fs::read(&path)?)?)?;
+        if remove_sealed {
+            fs::remove_file(&path)?;
+        }
+        written.push(plain_path);
+    }
+    Ok(written)
+}
+
+fn day_files(dir: &Path, ext: &str) -> io::Result<Vec<PathBuf>> {
+    let suffix = if ext == ENC_EXT { ".txt.enc" } else { ".txt" };
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.file_name()
+                .and_then(|s| s.to_str())
+                .is_some_and(|s| s.starts_with("day_") && s.ends_with(suffix))
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_test() {
+        let key = generate_key();
+        let sealed = encrypt(&key, b"3   4\n4   3\n");
+        assert_ne!(&sealed[MAGIC.len() + NONCE_LEN..], b"3   4\n4   3\n");
+        assert_eq!(decrypt(&key, &sealed).unwrap(), b"3   4\n4   3\n");
+
+        let other = generate_key();
+        assert!(decrypt(&other, &sealed).is_err());
+    }
+
+    #[test]
+    fn key_test() {
+        let key = generate_key();
+        assert_eq!(parse_key(&format_key(&key)).unwrap(), key);
+        assert!(parse_key("abc").is_err());
+    }
+
+    #[test]
+    fn dir_test() {
+        let dir = env::temp_dir().join(format!("aoc_input_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("day_01.txt"), "1   2\n").unwrap();
+        let key = generate_key();
+
+        let sealed = encrypt_dir(&dir, &key, true).unwrap();
+        assert_eq!(sealed, vec![dir.join("day_01.txt.enc")]);
+        assert!(!dir.join("day_01.txt").exists());
+
+        let plain = decrypt_dir(&dir, &key, false).unwrap();
+        assert_eq!(plain, vec![dir.join("day_01.txt")]);
+        assert_eq!(fs::read_to_string(&plain[0]).unwrap(), "1   2\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
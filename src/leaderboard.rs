@@ -0,0 +1,268 @@
+//! Private leaderboard analysis for `aoc2024 leaderboard`.
+//!
+//! Reads the JSON of a private leaderboard, from a file, a URL or a leaderboard
+//! id fetched from the base URL with the `AOC_SESSION` cookie. Star times are
+//! shown relative to the puzzle unlock (midnight EST, 05:00 UTC).
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::ffi::AOC_YEAR;
+use crate::http;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    #[serde(default)]
+    pub owner_id: u64,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub stars: u32,
+    #[serde(default)]
+    pub local_score: u64,
+    /// day -> part -> star
+    #[serde(default)]
+    pub completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+    #[serde(default)]
+    pub star_index: u64,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    pub fn star(&self, day: u8, part: u8) -> Option<Star> {
+        self.completion_day_level
+            .get(&day.to_string())?
+            .get(&part.to_string())
+            .copied()
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> io::Result<Leaderboard> {
+        serde_json::from_str(json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn year(&self) -> i64 {
+        self.event.parse().unwrap_or(AOC_YEAR as i64)
+    }
+
+    /// Members sorted by id, so reports are stable.
+    pub fn sorted_members(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by_key(|m| m.id);
+        members
+    }
+
+    /// Local scores recomputed from the star times: for every star, the first
+    /// member to get it scores one point per member, the next one less, and so on.
+    pub fn local_scores(&self) -> HashMap<u64, u64> {
+        let n = self.members.len() as u64;
+        let mut scores: HashMap<u64, u64> = self.members.values().map(|m| (m.id, 0)).collect();
+        for day in 1..=25 {
+            for part in 1..=2 {
+                let mut finishers: Vec<(i64, u64, u64)> = self
+                    .members
+                    .values()
+                    .filter_map(|m| {
+                        m.star(day, part)
+                            .map(|s| (s.get_star_ts, s.star_index, m.id))
+                    })
+                    .collect();
+                finishers.sort_unstable();
+                for (rank, &(_, _, id)) in finishers.iter().enumerate() {
+                    *scores.entry(id).or_default() += n - rank as u64;
+                }
+            }
+        }
+        scores
+    }
+
+    pub fn report(&self) -> String {
+        let scores = self.local_scores();
+        let mut members = self.sorted_members();
+        members.sort_by_key(|m| std::cmp::Reverse(scores[&m.id]));
+
+        let mut s = String::new();
+        let _ = writeln!(s, "{} leaderboard, {} members\n", self.event, members.len());
+        let _ = writeln!(
+            s,
+            "{:<30} {:>5} {:>6} {:>8}",
+            "member", "stars", "score", "reported"
+        );
+        for m in &members {
+            let mark = if scores[&m.id] == m.local_score {
+                ""
+            } else {
+                " *"
+            };
+            let _ = writeln!(
+                s,
+                "{:<30} {:>5} {:>6} {:>8}{}",
+                m.display_name(),
+                m.stars,
+                scores[&m.id],
+                m.local_score,
+                mark
+            );
+        }
+        if members.iter().any(|m| scores[&m.id] != m.local_score) {
+            let _ = writeln!(s, "* the reported score differs from the recomputed one");
+        }
+
+        for m in &members {
+            let _ = writeln!(s, "\n{}", m.display_name());
+            let _ = writeln!(
+                s,
+                "  {:<4} {:>12} {:>12} {:>12}",
+                "day", "part 1", "part 2", "delta"
+            );
+            for day in 1..=25 {
+                let unlock = unlock_ts(self.year(), day);
+                let (a, b) = (m.star(day, 1), m.star(day, 2));
+                if a.is_none() && b.is_none() {
+                    continue;
+                }
+                let since = |star: Option<Star>| {
+                    star.map_or("-".to_string(), |s| format_elapsed(s.get_star_ts - unlock))
+                };
+                let delta = match (a, b) {
+                    (Some(a), Some(b)) => format_elapsed(b.get_star_ts - a.get_star_ts),
+                    _ => "-".to_string(),
+                };
+                let _ = writeln!(
+                    s,
+                    "  {:<4} {:>12} {:>12} {:>12}",
+                    day,
+                    since(a),
+                    since(b),
+                    delta
+                );
+            }
+        }
+        s
+    }
+}
+
+/// Reads leaderboard JSON from a file, an `http(s)://` URL, or a leaderboard id
+/// fetched from `base_url`. The session cookie is only sent to `base_url`.
+pub fn load(source: &str, base_url: &str, session: Option<&str>) -> io::Result<String> {
+    if source.starts_with("http://") || source.starts_with("https://") {
+        let session = session.filter(|_| http::same_origin(source, base_url));
+        return http::get(source, session);
+    }
+    if !Path::new(source).exists() && source.chars().all(|c| c.is_ascii_digit()) {
+        let url = format!(
+            "{}/{}/leaderboard/private/view/{}.json",
+            base_url.trim_end_matches('/'),
+            AOC_YEAR,
+            source
+        );
+        return http::get(&url, session);
+    }
+    fs::read_to_string(source)
+}
+
+/// Unix time of the day's puzzle unlock, midnight EST on day `day` of December.
+pub fn unlock_ts(year: i64, day: u8) -> i64 {
+    (days_from_civil(year, 12, day as i64)) * 86_400 + 5 * 3_600
+}
+
+// days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * (m + if m > 2 { -3 } else { 9 }) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// `HH:MM:SS`, with days in front past 24 hours.
+pub fn format_elapsed(secs: i64) -> String {
+    let (sign, secs) = if secs < 0 { ("-", -secs) } else { ("", secs) };
+    let (d, h, m, s) = (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60, secs % 60);
+    if d > 0 {
+        format!("{}{}d {:02}:{:02}:{:02}", sign, d, h, m, s)
+    } else {
+        format!("{}{:02}:{:02}:{:02}", sign, h, m, s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNLOCK_DAY_1: i64 = 1_733_029_200;
+
+    fn json() -> String {
+        let star = |offset: i64, index: u64| {
+            format!(
+                r#"{{"get_star_ts":{},"star_index":{}}}"#,
+                UNLOCK_DAY_1 + offset,
+                index
+            )
+        };
+        format!(
+            r#"{{"event":"2024","owner_id":1,"members":{{
+                "1":{{"id":1,"name":"ann","stars":3,"local_score":5,"global_score":0,"last_star_ts":0,
+                      "completion_day_level":{{"1":{{"1":{},"2":{}}},"2":{{"1":{}}}}}}},
+                "2":{{"id":2,"name":null,"stars":2,"local_score":4,"global_score":0,"last_star_ts":0,
+                      "completion_day_level":{{"1":{{"1":{},"2":{}}}}}}}}}}}"#,
+            star(100, 1),
+            star(400, 4),
+            star(86_400 + 50, 5),
+            star(200, 2),
+            star(300, 3),
+        )
+    }
+
+    #[test]
+    fn local_scores_test() {
+        let board = Leaderboard::parse(&json()).unwrap();
+        let scores = board.local_scores();
+        // day 1: ann first on part 1, second on part 2, day 2 part 1 alone
+        assert_eq!(Some(&5), scores.get(&1));
+        assert_eq!(Some(&3), scores.get(&2));
+    }
+
+    #[test]
+    fn report_test() {
+        let report = Leaderboard::parse(&json()).unwrap().report();
+        assert!(report.contains("(anonymous user #2)"));
+        // ann's day 1 stars 100s and 400s after unlock, 5 minutes apart
+        assert!(report.contains("  1        00:01:40     00:06:40     00:05:00"));
+        assert!(report.contains("  2        00:00:50            -            -"));
+        // the reported 4 for user 2 doesn't match the recomputed 3
+        assert!(report.contains("* the reported score differs"));
+    }
+
+    #[test]
+    fn unlock_ts_test() {
+        assert_eq!(UNLOCK_DAY_1, unlock_ts(2024, 1));
+        assert_eq!(UNLOCK_DAY_1 + 24 * 86_400, unlock_ts(2024, 25));
+        assert_eq!(
+            "1d 02:03:04",
+            format_elapsed(86_400 + 2 * 3_600 + 3 * 60 + 4)
+        );
+    }
+}
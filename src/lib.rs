@@ -1,5 +1,9 @@
 #![feature(slice_swap_unchecked)]
 
+pub mod answers;
+pub mod api;
+pub mod cache;
+pub mod cold;
 pub mod common;
 pub mod day_01;
 pub mod day_02;
@@ -10,6 +14,20 @@ pub mod day_06;
 pub mod day_07;
 pub mod day_08;
 pub mod day_09;
-pub mod day_10;
-pub mod day_11;
+pub mod days;
+pub mod error;
+pub mod examples;
+pub mod ffi;
+#[doc(hidden)]
+pub mod fuzzing;
+pub mod history;
+pub mod http;
+pub mod input;
+pub mod leaderboard;
 pub mod perms;
+pub mod plugin;
+pub mod puzzle;
+pub mod report;
+pub mod viz;
+
+pub use api::{solve, Answer, Part};
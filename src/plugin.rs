@@ -0,0 +1,238 @@
+//! Day solvers loaded from shared libraries at startup.
+//!
+//! Every shared library in the plugin directory (`plugins/`, or `AOC_PLUGIN_DIR`)
+//! is opened and asked for its days, so alternative solvers can be tried without
+//! rebuilding `aoc2024`. A plugin exports two C functions:
+//!
+//! - `aoc_plugin_abi_version`, returning the [`AOC_PLUGIN_ABI_VERSION`] it was built for,
+//!   a plugin built for another version is refused;
+//! - `aoc_plugin_register`, calling the given [`AocRegisterFn`] once per day it solves.
+//!
+//! A day's [`AocPluginSolveFn`] works like `aoc_solve` and returns the same `AOC_`
+//! status codes. It must not unwind, a panic crossing it aborts the runner.
+//! See `examples/plugin_day_01.rs` for a plugin written in Rust.
+use std::env;
+use std::ffi::{c_void, CStr};
+use std::fs;
+use std::io;
+use std::os::raw::{c_char, c_int};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use libloading::Library;
+
+use crate::api::Part;
+use crate::common::{Solution, Timings};
+use crate::error::{AocError, Result};
+use crate::ffi::{AocBuffer, AOC_ERR_BUFFER, AOC_OK};
+
+/// Bumped whenever the plugin types or entry points change.
+pub const AOC_PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Solves `part` (1 or 2) for the `len` bytes of input at `input_ptr`, writing the
+/// answer (or an error message) to `out_buf` and returning an `AOC_` status code.
+pub type AocPluginSolveFn = unsafe extern "C" fn(
+    part: u32,
+    input_ptr: *const u8,
+    len: usize,
+    out_buf: *mut AocBuffer,
+) -> c_int;
+
+/// One solver registered by a plugin, `name` tells it apart from the built in solver.
+#[repr(C)]
+pub struct AocPluginDay {
+    pub day: u32,
+    /// NUL terminated, copied during registration.
+    pub name: *const c_char,
+    pub solve: AocPluginSolveFn,
+}
+
+/// Passed to `aoc_plugin_register`, to be called with its `ctx` for every day.
+pub type AocRegisterFn = unsafe extern "C" fn(ctx: *mut c_void, day: *const AocPluginDay);
+
+type AbiVersionFn = unsafe extern "C" fn() -> u32;
+type RegisterEntryFn = unsafe extern "C" fn(register: AocRegisterFn, ctx: *mut c_void);
+
+pub fn plugin_dir() -> PathBuf {
+    env::var_os("AOC_PLUGIN_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/plugins")))
+}
+
+/// A solver from a loaded plugin, only valid while its [`Plugins`] is.
+#[derive(Debug)]
+pub struct PluginDay {
+    pub day: u8,
+    pub name: String,
+    /// The library it came from.
+    pub path: PathBuf,
+    solve: AocPluginSolveFn,
+}
+
+impl PluginDay {
+    pub fn solve_part(&self, part: Part, input: &str) -> Result<String> {
+        let part = match part {
+            Part::A => 1,
+            Part::B => 2,
+        };
+        let mut data: Vec<c_char> = vec![0; 64];
+        loop {
+            let mut out = AocBuffer {
+                data: data.as_mut_ptr(),
+                capacity: data.len(),
+                len: 0,
+            };
+            let status = unsafe { (self.solve)(part, input.as_ptr(), input.len(), &mut out) };
+            if status == AOC_ERR_BUFFER && out.len >= data.len() {
+                data.resize(out.len + 1, 0);
+                continue;
+            }
+            let bytes: Vec<u8> = data[..out.len.min(data.len())]
+                .iter()
+                .map(|&c| c as u8)
+                .collect();
+            let text = String::from_utf8_lossy(&bytes).into_owned();
+            return match status {
+                AOC_OK => Ok(text),
+                _ => Err(AocError::new(
+                    self.day,
+                    format!("plugin {} failed ({}): {}", self.name, status, text),
+                )),
+            };
+        }
+    }
+
+    /// Runs both parts like a built in `solve_day_XX`, the answers are labelled as usual.
+    pub fn solve(&self, input: &str) -> Result<Solution> {
+        let start = Instant::now();
+        let a = self.solve_part(Part::A, input)?;
+        let solved_a = start.elapsed();
+        let b = self.solve_part(Part::B, input)?;
+        let duration = start.elapsed();
+        // a plugin parses within each part
+        Ok(Solution {
+            part_a: format!("day_{:02}_a = {}", self.day, a),
+            part_b: format!("day_{:02}_b = {}", self.day, b),
+            duration,
+            timings: Timings::from_marks(Duration::ZERO, solved_a, duration),
+        })
+    }
+}
+
+/// Loaded plugins, the libraries stay open as long as their days are usable.
+#[derive(Default)]
+pub struct Plugins {
+    // declared before the libraries so the function pointers go first
+    days: Vec<PluginDay>,
+    libraries: Vec<Library>,
+}
+
+impl Plugins {
+    /// Loads every shared library in `dir`, a missing directory has no plugins.
+    /// Libraries that fail to load are returned with their error instead.
+    pub fn load_dir(dir: &Path) -> (Plugins, Vec<(PathBuf, io::Error)>) {
+        let mut plugins = Plugins::default();
+        let mut failed = Vec::new();
+        let mut paths: Vec<PathBuf> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| {
+                    p.extension()
+                        .is_some_and(|ext| ext == env::consts::DLL_EXTENSION)
+                })
+                .collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return (plugins, vec![(dir.to_path_buf(), e)]),
+        };
+        paths.sort();
+        for path in paths {
+            if let Err(e) = plugins.load(&path) {
+                failed.push((path, e));
+            }
+        }
+        (plugins, failed)
+    }
+
+    /// Loads one plugin library, returning how many days it registered.
+    pub fn load(&mut self, path: &Path) -> io::Result<usize> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+        // loading runs the library's initializers, a plugin is trusted like the runner itself
+        let library = unsafe { Library::new(path) }.map_err(|e| invalid(e.to_string()))?;
+        let days = unsafe {
+            let version = library
+                .get::<AbiVersionFn>(b"aoc_plugin_abi_version\0")
+                .map_err(|_| invalid("not an aoc2024 plugin".to_string()))?;
+            let version = version();
+            if version != AOC_PLUGIN_ABI_VERSION {
+                return Err(invalid(format!(
+                    "plugin ABI version {}, expected {}",
+                    version, AOC_PLUGIN_ABI_VERSION
+                )));
+            }
+            let entry = library
+                .get::<RegisterEntryFn>(b"aoc_plugin_register\0")
+                .map_err(|_| invalid("no aoc_plugin_register entry point".to_string()))?;
+            let mut registered: Vec<Registered> = Vec::new();
+            entry(
+                register,
+                &mut registered as *mut Vec<Registered> as *mut c_void,
+            );
+            registered
+        };
+
+        let mut added = Vec::new();
+        for r in days {
+            let Some(day) = u8::try_from(r.day).ok().filter(|d| (1..=25).contains(d)) else {
+                return Err(invalid(format!(
+                    "{} registered unknown day {}",
+                    r.name, r.day
+                )));
+            };
+            added.push(PluginDay {
+                day,
+                name: r.name,
+                path: path.to_path_buf(),
+                solve: r.solve,
+            });
+        }
+        let count = added.len();
+        self.days.extend(added);
+        self.libraries.push(library);
+        Ok(count)
+    }
+
+    pub fn days(&self) -> &[PluginDay] {
+        &self.days
+    }
+
+    pub fn for_day(&self, day: u8) -> impl Iterator<Item = &PluginDay> {
+        self.days.iter().filter(move |d| d.day == day)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty()
+    }
+}
+
+struct Registered {
+    day: u32,
+    name: String,
+    solve: AocPluginSolveFn,
+}
+
+unsafe extern "C" fn register(ctx: *mut c_void, day: *const AocPluginDay) {
+    if ctx.is_null() || day.is_null() {
+        return;
+    }
+    let registered = &mut *(ctx as *mut Vec<Registered>);
+    let day = &*day;
+    let name = match day.name.is_null() {
+        true => format!("plugin_{}", registered.len() + 1),
+        false => CStr::from_ptr(day.name).to_string_lossy().into_owned(),
+    };
+    registered.push(Registered {
+        day: day.day,
+        name,
+        solve: day.solve,
+    });
+}
@@ -0,0 +1,477 @@
+//! Puzzle descriptions for `aoc2024 puzzle`.
+//!
+//! A day's page is fetched once from `AOC_BASE_URL` (default adventofcode.com)
+//! and kept in `AOC_PUZZLE_DIR` (default `.aoc_puzzles/` in the crate root, not
+//! committed, the puzzle text belongs to Advent of Code). Part two is only on the
+//! page for a logged in user who solved part one, so `AOC_SESSION` is sent as the
+//! session cookie when set and a cached page without part two is fetched again.
+//!
+//! The pages use a small set of tags, enough for a tokenizer and two renderers.
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::ffi::AOC_YEAR;
+use crate::http;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+pub fn base_url() -> String {
+    env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+}
+
+pub fn puzzle_dir() -> PathBuf {
+    env::var_os("AOC_PUZZLE_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/.aoc_puzzles")))
+}
+
+pub fn session() -> Option<String> {
+    env::var("AOC_SESSION")
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+}
+
+pub fn page_file(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day_{:02}.html", day))
+}
+
+pub fn fetch(base_url: &str, day: u8, session: Option<&str>) -> io::Result<String> {
+    let url = format!(
+        "{}/{}/day/{}",
+        base_url.trim_end_matches('/'),
+        AOC_YEAR,
+        day
+    );
+    http::get(&url, session)
+}
+
+/// The cached page of `day`, fetched when missing, on `refresh`, or when it lacks
+/// part two and a session could unlock it.
+pub fn load_page(
+    dir: &Path,
+    base_url: &str,
+    day: u8,
+    session: Option<&str>,
+    refresh: bool,
+) -> io::Result<String> {
+    let path = page_file(dir, day);
+    match fs::read_to_string(&path) {
+        Ok(html) if !refresh && (articles(&html).len() > 1 || session.is_none()) => {
+            return Ok(html)
+        }
+        Ok(_) => (),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+        Err(e) => return Err(e),
+    }
+    let html = fetch(base_url, day, session)?;
+    fs::create_dir_all(dir)?;
+    fs::write(&path, &html)?;
+    Ok(html)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Token {
+    Open {
+        name: String,
+        attrs: Vec<(String, String)>,
+    },
+    Close(String),
+    Text(String),
+}
+
+impl Token {
+    pub fn attr(&self, key: &str) -> Option<&str> {
+        match self {
+            Token::Open { attrs, .. } => attrs.iter().find(|(k, _)| k == key).map(|(_, v)| &v[..]),
+            _ => None,
+        }
+    }
+}
+
+/// Splits html into tags and decoded text, comments and `<!doctype>` are dropped.
+pub fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, r)| r);
+        } else if let Some(end) = rest.find('>').filter(|_| rest.starts_with('<')) {
+            let tag = &rest[1..end];
+            rest = &rest[end + 1..];
+            if let Some(name) = tag.strip_prefix('/') {
+                tokens.push(Token::Close(name.trim().to_ascii_lowercase()));
+            } else if !tag.starts_with('!') {
+                let tag = tag.trim_end_matches('/');
+                let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+                tokens.push(Token::Open {
+                    name: name.to_ascii_lowercase(),
+                    attrs: parse_attrs(attrs),
+                });
+            }
+        } else {
+            // a '<' without a closing '>' is text, skip its first char whatever its width
+            let first = rest.chars().next().map_or(1, char::len_utf8);
+            let end = rest[first..].find('<').map_or(rest.len(), |i| i + first);
+            tokens.push(Token::Text(decode_entities(&rest[..end])));
+            rest = &rest[end..];
+        }
+    }
+    tokens
+}
+
+fn parse_attrs(s: &str) -> Vec<(String, String)> {
+    let mut attrs = Vec::new();
+    let mut rest = s.trim();
+    while !rest.is_empty() {
+        let key_end = rest.find(['=', ' ']).unwrap_or(rest.len());
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+        let mut value = String::new();
+        if let Some(r) = rest.strip_prefix('=') {
+            let r = r.trim_start();
+            let (v, r) = match r.chars().next() {
+                Some(q @ ('"' | '\'')) => r[1..].split_once(q).unwrap_or((&r[1..], "")),
+                _ => r.split_once(' ').unwrap_or((r, "")),
+            };
+            value = decode_entities(v);
+            rest = r.trim_start();
+        }
+        attrs.push((key, value));
+    }
+    attrs
+}
+
+pub fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let decoded = rest.find(';').filter(|&j| j < 10).and_then(|j| {
+            let ch = match &rest[1..j] {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                e => match e.strip_prefix("#x").or_else(|| e.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
+                    None => e
+                        .strip_prefix('#')
+                        .and_then(|d| d.parse().ok())
+                        .and_then(char::from_u32),
+                },
+            };
+            ch.map(|ch| (ch, j))
+        });
+        match decoded {
+            Some((ch, j)) => {
+                out.push(ch);
+                rest = &rest[j + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The tokens inside each `<article>` of the page, part one then part two.
+pub fn articles(html: &str) -> Vec<Vec<Token>> {
+    let mut articles = Vec::new();
+    let mut current: Option<Vec<Token>> = None;
+    for token in tokenize(html) {
+        match (&token, &mut current) {
+            (Token::Open { name, .. }, None) if name == "article" => current = Some(Vec::new()),
+            (Token::Close(name), Some(_)) if name == "article" => {
+                articles.extend(current.take());
+            }
+            (_, Some(tokens)) => tokens.push(token),
+            _ => (),
+        }
+    }
+    articles
+}
+
+/// Example inputs and answers found on a puzzle page.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Extracted {
+    /// Every distinct `<pre><code>` block, in page order.
+    pub blocks: Vec<String>,
+    /// The last `<code><em>` of each part, which is where the examples state their answer.
+    pub part_a: Option<String>,
+    pub part_b: Option<String>,
+}
+
+pub fn extract_examples(html: &str) -> Extracted {
+    let mut extracted = Extracted::default();
+    for (i, tokens) in articles(html).iter().enumerate() {
+        let (mut in_pre, mut in_code, mut in_em) = (false, false, false);
+        let mut text = String::new();
+        let mut answer = None;
+        for token in tokens {
+            match token {
+                Token::Open { name, .. } => match name.as_str() {
+                    "pre" => in_pre = true,
+                    "code" => in_code = true,
+                    "em" => in_em = true,
+                    _ => (),
+                },
+                Token::Close(name) => match name.as_str() {
+                    "pre" => {
+                        in_pre = false;
+                        let block = std::mem::take(&mut text);
+                        if !block.trim().is_empty() && !extracted.blocks.contains(&block) {
+                            extracted.blocks.push(block);
+                        }
+                    }
+                    "code" => in_code = false,
+                    "em" => in_em = false,
+                    _ => (),
+                },
+                Token::Text(t) if in_pre => text.push_str(t),
+                Token::Text(t) if in_code && in_em => answer = Some(t.trim().to_string()),
+                Token::Text(_) => (),
+            }
+        }
+        match i {
+            0 => extracted.part_a = answer,
+            1 => extracted.part_b = answer,
+            _ => (),
+        }
+    }
+    extracted
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Style {
+    Text,
+    Markdown,
+}
+
+/// Renders the articles of a puzzle page, paragraphs are wrapped at `width` in text style.
+pub fn render(html: &str, style: Style, width: usize) -> String {
+    let blocks: Vec<String> = articles(html)
+        .iter()
+        .flat_map(|tokens| render_article(tokens, style, width))
+        .collect();
+    blocks.join("\n\n") + "\n"
+}
+
+fn render_article(tokens: &[Token], style: Style, width: usize) -> Vec<String> {
+    let md = style == Style::Markdown;
+    let mut blocks = Vec::new();
+    let mut block = String::new();
+    let mut in_pre = false;
+    let mut href = None;
+    let finish = |block: &mut String, blocks: &mut Vec<String>| {
+        let text = std::mem::take(block);
+        if !text.trim().is_empty() {
+            blocks.push(text.trim_end().to_string());
+        }
+    };
+
+    for token in tokens {
+        match token {
+            Token::Open { name, .. } => match name.as_str() {
+                "h2" => {
+                    finish(&mut block, &mut blocks);
+                    if md {
+                        block.push_str("## ");
+                    }
+                }
+                "p" | "ul" => finish(&mut block, &mut blocks),
+                "li" => block.push_str(if block.is_empty() { "- " } else { "\n- " }),
+                "pre" => {
+                    finish(&mut block, &mut blocks);
+                    in_pre = true;
+                    if md {
+                        block.push_str("```\n");
+                    }
+                }
+                "code" if md && !in_pre => block.push('`'),
+                "em" if md => block.push('*'),
+                "a" if md => {
+                    href = token.attr("href").map(str::to_string);
+                    block.push('[');
+                }
+                _ => (),
+            },
+            Token::Close(name) => match name.as_str() {
+                "h2" | "p" | "ul" => {
+                    if !md && name != "ul" && name != "h2" {
+                        block = wrap(&block, width);
+                    }
+                    finish(&mut block, &mut blocks);
+                }
+                "pre" => {
+                    in_pre = false;
+                    if md {
+                        block = format!("{}\n```", block.trim_end());
+                    } else {
+                        block = block
+                            .trim_end()
+                            .lines()
+                            .map(|l| format!("    {}", l))
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                    }
+                    finish(&mut block, &mut blocks);
+                }
+                "code" if md && !in_pre => block.push('`'),
+                "em" if md => block.push('*'),
+                "a" if md => block.push_str(&format!("]({})", href.take().unwrap_or_default())),
+                _ => (),
+            },
+            Token::Text(text) if in_pre => block.push_str(text),
+            Token::Text(text) => {
+                // html whitespace collapses
+                let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                if text.starts_with(char::is_whitespace)
+                    && !block.is_empty()
+                    && !block.ends_with(' ')
+                {
+                    block.push(' ');
+                }
+                block.push_str(&collapsed);
+                if text.ends_with(char::is_whitespace) && !collapsed.is_empty() {
+                    block.push(' ');
+                }
+            }
+        }
+    }
+    finish(&mut block, &mut blocks);
+    blocks
+}
+
+fn wrap(s: &str, width: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line = String::new();
+    for word in s.split_whitespace() {
+        if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    lines.push(line);
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html><html><body><main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Pair up the <em>smallest</em> number
+with the <a href="/2024/about">smallest</a> one &amp; add <code>1 + 2</code>.</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<ul><li>One</li><li>Two</li></ul>
+</article>
+<p>Your puzzle answer was <code>11</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now <em>31</em>.</p><p>Übrigens — fin.</p></article>
+</main></body></html>"#;
+
+    #[test]
+    fn extract_examples_test() {
+        let page = r#"<article><p>For example:</p><pre><code>3   4
+4   3
+</code></pre><p>Within <code>4 3</code> the total is <code><em>11</em></code>.</p></article>
+<article><p>Again:</p><pre><code>3   4
+4   3
+</code></pre><p>The score is <code><em>9</em></code>, then <code><em>31</em></code>.</p>
+<pre><code>other</code></pre></article>"#;
+        let extracted = extract_examples(page);
+        assert_eq!(
+            vec!["3   4\n4   3\n".to_string(), "other".to_string()],
+            extracted.blocks
+        );
+        assert_eq!(
+            (Some("11".to_string()), Some("31".to_string())),
+            (extracted.part_a, extracted.part_b)
+        );
+    }
+
+    #[test]
+    fn tokenize_test() {
+        let tokens = tokenize(r#"<a href="/x?a=1&amp;b=2">&lt;3</a><!-- c --><br/>"#);
+        assert_eq!(
+            vec![
+                Token::Open {
+                    name: "a".to_string(),
+                    attrs: vec![("href".to_string(), "/x?a=1&b=2".to_string())]
+                },
+                Token::Text("<3".to_string()),
+                Token::Close("a".to_string()),
+                Token::Open {
+                    name: "br".to_string(),
+                    attrs: vec![]
+                },
+            ],
+            tokens
+        );
+        assert_eq!("a > b ' &x", decode_entities("a &gt; b &#39; &x"));
+        let text = |s: &str| Token::Text(s.to_string());
+        assert_eq!(vec![text("1 "), text("< 2")], tokenize("1 < 2"));
+        assert_eq!(vec![text("—")], tokenize("—"));
+        assert_eq!(
+            vec![
+                Token::Open {
+                    name: "p".to_string(),
+                    attrs: vec![]
+                },
+                text("été"),
+                Token::Close("p".to_string()),
+            ],
+            tokenize("<p>été</p>")
+        );
+    }
+
+    #[test]
+    fn articles_test() {
+        assert_eq!(2, articles(PAGE).len());
+    }
+
+    #[test]
+    fn markdown_test() {
+        let md = render(PAGE, Style::Markdown, 80);
+        assert_eq!(
+            "## --- Day 1: Test ---\n\n\
+             Pair up the *smallest* number with the [smallest](/2024/about) one & add `1 + 2`.\n\n\
+             ```\n3   4\n4   3\n```\n\n\
+             - One\n- Two\n\n\
+             ## --- Part Two ---\n\n\
+             Now *31*.\n\n\
+             Übrigens — fin.\n",
+            md
+        );
+    }
+
+    #[test]
+    fn text_test() {
+        let text = render(PAGE, Style::Text, 30);
+        assert_eq!(
+            "--- Day 1: Test ---\n\n\
+             Pair up the smallest number\nwith the smallest one & add 1\n+ 2.\n\n\
+             \x20   3   4\n    4   3\n\n\
+             - One\n- Two\n\n\
+             --- Part Two ---\n\n\
+             Now 31.\n\n\
+             Übrigens — fin.\n",
+            text
+        );
+    }
+}
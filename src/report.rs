@@ -0,0 +1,361 @@
+//! Self contained HTML report for `aoc2024 report`.
+//!
+//! One page with each day's answers, a stacked bar chart of its parse / part A /
+//! part B times, trend lines of the times over the runs in the history file and
+//! a link to each day's source. The charts are inline SVG, so the file can be
+//! mailed or attached without anything next to it.
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::answers::REDACTED;
+use crate::api::answer_value;
+use crate::common::{Solution, Timings};
+use crate::days::DAYS;
+use crate::history::Run;
+use crate::viz::{Rgb, MARK, PATH, START, WALL};
+
+/// Where day sources are linked to by default, relative to a report in the crate root.
+pub const SOURCE_URL: &str = "src";
+
+// number of past runs the trend lines show
+const TREND_RUNS: usize = 30;
+
+const PARSE_COLOR: Rgb = WALL;
+const PART_A_COLOR: Rgb = START;
+const PART_B_COLOR: Rgb = PATH;
+
+/// A day's line in the report, answers are `None` when it comes from the history.
+#[derive(Clone, Debug, Default)]
+pub struct DayRow {
+    pub day: u8,
+    pub part_a: Option<String>,
+    pub part_b: Option<String>,
+    pub timings: Timings,
+    pub error: Option<String>,
+}
+
+impl DayRow {
+    pub fn from_solution(day: u8, solution: &Solution) -> Self {
+        DayRow {
+            day,
+            part_a: Some(answer_value(&solution.part_a).to_string()),
+            part_b: Some(answer_value(&solution.part_b).to_string()),
+            timings: solution.timings,
+            error: None,
+        }
+    }
+
+    /// The timings of the last run in the history, without answers.
+    pub fn from_history(run: &Run) -> Vec<DayRow> {
+        run.days
+            .iter()
+            .map(|&(day, timings)| DayRow {
+                day,
+                timings,
+                ..DayRow::default()
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub redact: bool,
+    /// Day sources are linked as `{source_url}/day_XX.rs`.
+    pub source_url: String,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            redact: false,
+            source_url: SOURCE_URL.to_string(),
+        }
+    }
+}
+
+pub fn render(rows: &[DayRow], history: &[Run], options: &Options) -> String {
+    let total: Duration = rows.iter().map(|r| r.timings.total()).sum();
+    let recent = &history[history.len().saturating_sub(TREND_RUNS)..];
+
+    let mut s = String::new();
+    s.push_str(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Advent of Code 2024</title>\n",
+    );
+    s.push_str(STYLE);
+    s.push_str("</head>\n<body>\n<h1>Advent of Code 2024</h1>\n");
+    let _ = writeln!(
+        s,
+        "<p>{} days in {}, {} runs in the history.</p>",
+        rows.len(),
+        format_duration(total),
+        history.len()
+    );
+
+    s.push_str("<table>\n<tr><th>day</th><th>part A</th><th>part B</th><th>parse</th><th>part A</th><th>part B</th><th>total</th><th>trend</th><th>source</th></tr>\n");
+    for row in rows {
+        let answer = |answer: &Option<String>| match (answer, &row.error) {
+            (_, Some(_)) => "<td class=\"fail\">failed</td>".to_string(),
+            (Some(_), _) if options.redact => format!("<td>{}</td>", REDACTED),
+            (Some(a), _) => format!("<td><code>{}</code></td>", escape(a)),
+            (None, _) => "<td>-</td>".to_string(),
+        };
+        let trend: Vec<Duration> = recent
+            .iter()
+            .filter_map(|run| run.get(row.day).map(|t| t.total()))
+            .collect();
+        let source = format!(
+            "{}/day_{:02}.rs",
+            options.source_url.trim_end_matches('/'),
+            row.day
+        );
+        let _ = writeln!(
+            s,
+            "<tr><td>{}</td>{}{}<td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td>{}</td><td><a href=\"{}\">day_{:02}.rs</a></td></tr>",
+            row.day,
+            answer(&row.part_a),
+            answer(&row.part_b),
+            format_duration(row.timings.parse),
+            format_duration(row.timings.part_a),
+            format_duration(row.timings.part_b),
+            format_duration(row.timings.total()),
+            sparkline(&trend),
+            escape(&source),
+            row.day
+        );
+    }
+    s.push_str("</table>\n");
+    for row in rows {
+        if let Some(e) = &row.error {
+            let _ = writeln!(s, "<p class=\"fail\">{}</p>", escape(e));
+        }
+    }
+
+    s.push_str("<h2>time per day</h2>\n");
+    s.push_str(&bar_chart(rows));
+    // only runs of every day, single day and partly cached runs would show as drops
+    let full: Vec<&Run> = history
+        .iter()
+        .filter(|run| DAYS.iter().all(|d| run.get(d.day).is_some()))
+        .collect();
+    let full = &full[full.len().saturating_sub(TREND_RUNS)..];
+    if full.len() > 1 {
+        s.push_str("<h2>total time per run</h2>\n");
+        let totals: Vec<Duration> = full
+            .iter()
+            .map(|run| run.days.iter().map(|(_, t)| t.total()).sum())
+            .collect();
+        s.push_str(&trend_chart(&totals));
+    }
+    s.push_str("</body>\n</html>\n");
+    s
+}
+
+const STYLE: &str = "<style>
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; }
+th, td { padding: 0.2em 0.8em; border-bottom: 1px solid #ddd; text-align: left; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+.fail { color: #c33; }
+.legend span { display: inline-block; width: 0.8em; height: 0.8em; margin: 0 0.3em 0 1em; }
+</style>
+";
+
+/// One bar per day, split into parse, part A and part B.
+pub fn bar_chart(rows: &[DayRow]) -> String {
+    let (label, width, bar, gap) = (60.0, 600.0, 18.0, 6.0);
+    let max = rows
+        .iter()
+        .map(|r| r.timings.total())
+        .max()
+        .unwrap_or_default()
+        .as_secs_f64();
+    let height = rows.len() as f64 * (bar + gap);
+    let mut s = format!(
+        "<p class=\"legend\"><span style=\"background:{}\"></span>parse<span style=\"background:{}\"></span>part A<span style=\"background:{}\"></span>part B</p>\n",
+        PARSE_COLOR.hex(),
+        PART_A_COLOR.hex(),
+        PART_B_COLOR.hex()
+    );
+    let _ = writeln!(
+        s,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-size=\"12\">",
+        label + width + 100.0,
+        height
+    );
+    for (i, row) in rows.iter().enumerate() {
+        let y = i as f64 * (bar + gap);
+        let _ = writeln!(
+            s,
+            "<text x=\"0\" y=\"{:.1}\">day {:02}</text>",
+            y + bar - 5.0,
+            row.day
+        );
+        let mut x = label;
+        let t = row.timings;
+        for (part, color) in [
+            (t.parse, PARSE_COLOR),
+            (t.part_a, PART_A_COLOR),
+            (t.part_b, PART_B_COLOR),
+        ] {
+            let w = if max > 0.0 {
+                part.as_secs_f64() / max * width
+            } else {
+                0.0
+            };
+            let _ = writeln!(
+                s,
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{}\" fill=\"{}\"><title>{}</title></rect>",
+                x,
+                y,
+                w,
+                bar,
+                color.hex(),
+                format_duration(part)
+            );
+            x += w;
+        }
+        let _ = writeln!(
+            s,
+            "<text x=\"{:.1}\" y=\"{:.1}\">{}</text>",
+            x + 4.0,
+            y + bar - 5.0,
+            format_duration(t.total())
+        );
+    }
+    s.push_str("</svg>\n");
+    s
+}
+
+/// Total time over the runs, oldest first.
+pub fn trend_chart(totals: &[Duration]) -> String {
+    let (width, height) = (600.0, 120.0);
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\">\n{}</svg>\n",
+        width,
+        height,
+        polyline(totals, width, height, MARK)
+    )
+}
+
+// a small trend line for a table cell, "-" with fewer than two points
+fn sparkline(values: &[Duration]) -> String {
+    if values.len() < 2 {
+        return "-".to_string();
+    }
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"80\" height=\"20\">{}</svg>",
+        polyline(values, 80.0, 20.0, MARK)
+    )
+}
+
+// values scaled to the box, the largest at the top, zero at the bottom
+fn polyline(values: &[Duration], width: f64, height: f64, color: Rgb) -> String {
+    let max = values
+        .iter()
+        .max()
+        .copied()
+        .unwrap_or_default()
+        .as_secs_f64();
+    let step = width / (values.len().max(2) - 1) as f64;
+    let points: Vec<String> = values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let y = if max > 0.0 {
+                1.0 + (1.0 - v.as_secs_f64() / max) * (height - 2.0)
+            } else {
+                height - 1.0
+            };
+            format!("{:.1},{:.1}", i as f64 * step, y)
+        })
+        .collect();
+    format!(
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"1.5\"/>",
+        points.join(" "),
+        color.hex()
+    )
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{:.2?}", d)
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<DayRow> {
+        let solution = Solution {
+            part_a: "day_01_a = 11".to_string(),
+            part_b: "day_01_b = 31".to_string(),
+            duration: Duration::from_micros(60),
+            timings: Timings {
+                parse: Duration::from_micros(10),
+                part_a: Duration::from_micros(20),
+                part_b: Duration::from_micros(30),
+            },
+        };
+        vec![DayRow::from_solution(1, &solution)]
+    }
+
+    // three runs of every day, then a run of day 1 only
+    fn history() -> Vec<Run> {
+        let timings = |us| Timings {
+            part_a: Duration::from_micros(us),
+            ..Timings::default()
+        };
+        let mut runs: Vec<Run> = (0..3)
+            .map(|i| Run {
+                timestamp: i,
+                parallel: false,
+                days: DAYS.iter().map(|d| (d.day, timings(10 + i))).collect(),
+            })
+            .collect();
+        runs.push(Run {
+            timestamp: 3,
+            parallel: false,
+            days: vec![(1, timings(1))],
+        });
+        runs
+    }
+
+    #[test]
+    fn render_test() {
+        let html = render(&rows(), &history(), &Options::default());
+        assert!(html.contains("<td><code>11</code></td><td><code>31</code></td>"));
+        assert!(html.contains("<a href=\"src/day_01.rs\">day_01.rs</a>"));
+        // three segments in the bar chart, a sparkline and the run trend
+        assert_eq!(3, html.matches("<rect").count());
+        assert_eq!(2, html.matches("<polyline").count());
+        // a single full run has no run trend
+        let html = render(&rows(), &history()[2..], &Options::default());
+        assert_eq!(1, html.matches("<polyline").count());
+
+        let options = Options {
+            redact: true,
+            source_url: "https://example.com/src/".to_string(),
+        };
+        let html = render(&rows(), &[], &options);
+        assert!(!html.contains("<code>11</code>"));
+        assert!(html.contains(REDACTED));
+        assert!(html.contains("href=\"https://example.com/src/day_01.rs\""));
+        assert_eq!(0, html.matches("<polyline").count());
+    }
+
+    #[test]
+    fn bar_chart_test() {
+        let svg = bar_chart(&rows());
+        // the longest day fills the chart: 10 + 20 + 30 of 60us over 600px
+        assert!(svg.contains("<rect x=\"60.0\" y=\"0.0\" width=\"100.0\""));
+        assert!(svg.contains("<rect x=\"160.0\" y=\"0.0\" width=\"200.0\""));
+        assert!(svg.contains("<rect x=\"360.0\" y=\"0.0\" width=\"300.0\""));
+    }
+}
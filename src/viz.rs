@@ -0,0 +1,349 @@
+//! Pictures of puzzle state for `aoc2024 viz`.
+//!
+//! A day's `visualize` hook draws a [`Canvas`] of coloured characters, which is
+//! rendered as ANSI text for the terminal or as an SVG / PNG / PPM image where
+//! every cell becomes a `scale` x `scale` square of its colour. Any [`Grid`] can be
+//! saved as an image the same way given the colour of each cell.
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::common::render::{Renderer, Style};
+use crate::common::{Grid, Point};
+
+pub use crate::common::color::{palette, Rgb, BACKGROUND, DIM, MARK, MARK_DIM, PATH, START, WALL};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cell {
+    pub ch: char,
+    pub color: Rgb,
+}
+
+#[derive(Clone, Debug)]
+pub struct Canvas {
+    pub rows: usize,
+    pub cols: usize,
+    cells: Vec<Cell>,
+}
+
+impl Canvas {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        let blank = Cell {
+            ch: ' ',
+            color: BACKGROUND,
+        };
+        Canvas {
+            rows,
+            cols,
+            cells: vec![blank; rows * cols],
+        }
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Cell {
+        self.cells[row * self.cols + col]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, ch: char, color: Rgb) {
+        self.cells[row * self.cols + col] = Cell { ch, color };
+    }
+
+    pub fn count(&self, ch: char) -> usize {
+        self.cells.iter().filter(|c| c.ch == ch).count()
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        self.cells
+            .chunks(self.cols.max(1))
+            .map(|cs| cs.iter().map(|c| c.ch).collect())
+            .collect()
+    }
+
+    /// Every cell, for drawing with a [`Renderer`].
+    pub fn cells(&self) -> Grid<Cell> {
+        Grid::from_fn(self.rows, self.cols, |p| self.get(p.row, p.col))
+    }
+
+    /// The colour of every cell, for the image encoders.
+    pub fn colors(&self) -> Grid<Rgb> {
+        Grid::from_fn(self.rows, self.cols, |p| self.get(p.row, p.col).color)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Ansi,
+    Svg,
+    Png,
+    Ppm,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Ansi => "ans",
+            Format::Svg => "svg",
+            Format::Png => "png",
+            Format::Ppm => "ppm",
+        }
+    }
+
+    /// The image format of a file name's extension.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        match path.extension()?.to_str()? {
+            "ans" => Some(Format::Ansi),
+            ext => ext.parse().ok(),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Ansi => write!(f, "ansi"),
+            _ => write!(f, "{}", self.extension()),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "ansi" => Ok(Format::Ansi),
+            "svg" => Ok(Format::Svg),
+            "png" => Ok(Format::Png),
+            "ppm" => Ok(Format::Ppm),
+            _ => Err(format!(
+                "unknown format '{}', expected ansi, svg, png or ppm",
+                s
+            )),
+        }
+    }
+}
+
+pub fn viz_dir() -> PathBuf {
+    env::var_os("AOC_VIZ_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/viz")))
+}
+
+pub fn render(canvas: &Canvas, format: Format, scale: usize) -> io::Result<Vec<u8>> {
+    match format {
+        Format::Ansi => Ok(to_ansi(canvas, true).into_bytes()),
+        _ => encode(&canvas.colors(), format, scale),
+    }
+}
+
+/// An image with a `scale` x `scale` square per cell. ANSI has a block character
+/// per cell instead. Images need at least one cell and a `scale` above zero.
+pub fn encode(colors: &Grid<Rgb>, format: Format, scale: usize) -> io::Result<Vec<u8>> {
+    if format != Format::Ansi && (colors.rows == 0 || colors.cols == 0 || scale == 0) {
+        let msg = format!(
+            "no image of a {}x{} grid at scale {}",
+            colors.rows, colors.cols, scale
+        );
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+    }
+    Ok(match format {
+        Format::Ansi => Renderer::new(colors, |_, &color| ('█', Style::fg(color)))
+            .color(true)
+            .render()
+            .into_bytes(),
+        Format::Svg => svg(colors, scale).into_bytes(),
+        Format::Png => png(colors, scale)?,
+        Format::Ppm => ppm(colors, scale),
+    })
+}
+
+/// An image of `grid` with each cell in `color(cell)`.
+pub fn grid_image<T>(
+    grid: &Grid<T>,
+    format: Format,
+    scale: usize,
+    color: impl FnMut(&T) -> Rgb,
+) -> io::Result<Vec<u8>> {
+    encode(&grid.map(color), format, scale)
+}
+
+/// Writes an image of `grid` to `path`, in the format of its extension.
+pub fn save_grid<T>(
+    grid: &Grid<T>,
+    path: &Path,
+    scale: usize,
+    color: impl FnMut(&T) -> Rgb,
+) -> io::Result<()> {
+    let format = Format::from_path(path).ok_or_else(|| {
+        let msg = format!("{} is not a .ans, .svg, .png or .ppm file", path.display());
+        io::Error::new(io::ErrorKind::InvalidInput, msg)
+    })?;
+    fs::write(path, grid_image(grid, format, scale, color)?)
+}
+
+/// The characters of the canvas, with 24 bit colour escapes when `color`.
+pub fn to_ansi(canvas: &Canvas, color: bool) -> String {
+    Renderer::new(&canvas.cells(), |_, cell| (cell.ch, Style::fg(cell.color)))
+        .color(color)
+        .render()
+}
+
+pub fn to_svg(canvas: &Canvas, scale: usize) -> String {
+    svg(&canvas.colors(), scale)
+}
+
+pub fn to_png(canvas: &Canvas, scale: usize) -> io::Result<Vec<u8>> {
+    png(&canvas.colors(), scale)
+}
+
+/// One rect per horizontal run of a colour, on a background rect.
+pub fn svg(colors: &Grid<Rgb>, scale: usize) -> String {
+    let (w, h) = (colors.cols * scale, colors.rows * scale);
+    let mut s = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n<rect width=\"{w}\" height=\"{h}\" fill=\"{}\"/>\n",
+        BACKGROUND.hex()
+    );
+    for (row, cells) in colors.iter_rows().enumerate() {
+        let mut col = 0;
+        while col < colors.cols {
+            let color = cells[col];
+            let run = cells[col..].iter().take_while(|&&c| c == color).count();
+            if color != BACKGROUND {
+                s.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+                    col * scale,
+                    row * scale,
+                    run * scale,
+                    scale,
+                    color.hex()
+                ));
+            }
+            col += run;
+        }
+    }
+    s.push_str("</svg>\n");
+    s
+}
+
+/// Fails for an empty image, which PNG has no encoding for.
+pub fn png(colors: &Grid<Rgb>, scale: usize) -> io::Result<Vec<u8>> {
+    let (w, h) = (colors.cols * scale, colors.rows * scale);
+    let pixels = pixels(colors, scale);
+
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, w as u32, h as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&pixels)?;
+    writer.finish()?;
+    Ok(out)
+}
+
+/// Binary PPM (P6), simple enough for any image viewer or converter.
+pub fn ppm(colors: &Grid<Rgb>, scale: usize) -> Vec<u8> {
+    let (w, h) = (colors.cols * scale, colors.rows * scale);
+    let mut out = format!("P6\n{} {}\n255\n", w, h).into_bytes();
+    out.extend(pixels(colors, scale));
+    out
+}
+
+// rgb bytes row by row, `scale` x `scale` per cell
+fn pixels(colors: &Grid<Rgb>, scale: usize) -> Vec<u8> {
+    let (w, h) = (colors.cols * scale, colors.rows * scale);
+    let mut pixels = Vec::with_capacity(w * h * 3);
+    for y in 0..h {
+        for x in 0..w {
+            let Rgb(r, g, b) = colors[Point::new(y / scale, x / scale)];
+            pixels.extend([r, g, b]);
+        }
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canvas() -> Canvas {
+        let mut canvas = Canvas::new(2, 3);
+        canvas.set(0, 0, '#', WALL);
+        canvas.set(0, 1, '#', WALL);
+        canvas.set(1, 2, 'X', PATH);
+        canvas
+    }
+
+    #[test]
+    fn ansi_test() {
+        let s = to_ansi(&canvas(), true);
+        assert_eq!(
+            "\x1b[0;38;2;150;150;160m##\x1b[0;38;2;16;16;24m \x1b[0m\n\x1b[0;38;2;16;16;24m  \x1b[0;38;2;240;200;60mX\x1b[0m\n",
+            s
+        );
+        assert_eq!("## \n  X\n", to_ansi(&canvas(), false));
+    }
+
+    #[test]
+    fn svg_test() {
+        let s = to_svg(&canvas(), 2);
+        assert!(s.contains("width=\"6\" height=\"4\""));
+        // the two walls are one run, blank cells are left to the background rect
+        assert!(s.contains("<rect x=\"0\" y=\"0\" width=\"4\" height=\"2\" fill=\"#9696a0\"/>"));
+        assert_eq!(3, s.matches("<rect").count());
+    }
+
+    #[test]
+    fn png_test() {
+        let bytes = to_png(&canvas(), 2).unwrap();
+        let decoder = png::Decoder::new(&bytes[..]);
+        let mut reader = decoder.read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).unwrap();
+        assert_eq!((6, 4), (info.width, info.height));
+        assert_eq!([150, 150, 160], buf[..3]);
+        // bottom right pixel is the path
+        assert_eq!([240, 200, 60], buf[buf.len() - 3..]);
+    }
+
+    #[test]
+    fn ppm_test() {
+        let bytes = encode(&canvas().colors(), Format::Ppm, 2).unwrap();
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(header, &bytes[..header.len()]);
+        assert_eq!(header.len() + 6 * 4 * 3, bytes.len());
+        assert_eq!([150, 150, 160], bytes[header.len()..header.len() + 3]);
+        assert_eq!([240, 200, 60], bytes[bytes.len() - 3..]);
+    }
+
+    #[test]
+    fn grid_image_test() {
+        let grid: Grid<char> = "#.\n.#\n".parse().unwrap();
+        let color = |&c: &char| if c == '#' { WALL } else { BACKGROUND };
+        let svg = String::from_utf8(grid_image(&grid, Format::Svg, 3, color).unwrap()).unwrap();
+        assert!(svg.contains("<rect x=\"3\" y=\"3\" width=\"3\" height=\"3\" fill=\"#9696a0\"/>"));
+
+        let path = env::temp_dir().join(format!("aoc_grid_image_{}.png", std::process::id()));
+        save_grid(&grid, &path, 1, color).unwrap();
+        assert_eq!(
+            grid_image(&grid, Format::Png, 1, color).unwrap(),
+            fs::read(&path).unwrap()
+        );
+        fs::remove_file(&path).unwrap();
+        assert!(save_grid(&grid, Path::new("grid.txt"), 1, color).is_err());
+        assert_eq!(Some(Format::Ppm), Format::from_path(Path::new("a/b.ppm")));
+    }
+
+    #[test]
+    fn empty_test() {
+        let empty = Canvas::new(0, 0);
+        assert!(empty.lines().is_empty());
+        assert!(to_png(&empty, 1).is_err());
+        for format in [Format::Svg, Format::Png, Format::Ppm] {
+            assert!(render(&empty, format, 1).is_err());
+            assert!(render(&canvas(), format, 0).is_err());
+        }
+        assert_eq!(Vec::<u8>::new(), render(&empty, Format::Ansi, 1).unwrap());
+    }
+}
@@ -0,0 +1,35 @@
+use lib::answers::{answers_file, Answers, Status};
+use lib::days::DAYS;
+use lib::input::input_available;
+
+// the committed expectations are the regression test for the real inputs
+#[test]
+fn answers_file_test() {
+    let answers = Answers::load(&answers_file()).unwrap();
+    assert!(!answers.is_empty());
+    for d in DAYS
+        .iter()
+        .filter(|d| answers.get(&format!("day_{:02}_a", d.day)).is_some())
+    {
+        if !input_available(d.day) {
+            eprintln!(
+                "skipped day {}, its input is encrypted and no key is configured",
+                d.day
+            );
+            continue;
+        }
+        let solution = (d.solve)().unwrap();
+        assert_eq!(
+            Status::Pass,
+            answers.check(&solution.part_a),
+            "day {}",
+            d.day
+        );
+        assert_eq!(
+            Status::Pass,
+            answers.check(&solution.part_b),
+            "day {}",
+            d.day
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use lib::{day_05, day_08, solve, Part};
+
+fn example(day: u8, name: &str) -> String {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/data/examples");
+    std::fs::read_to_string(format!("{}/day_{:02}/{}.txt", dir, day, name)).unwrap()
+}
+
+#[test]
+fn solve_test() {
+    let answer = solve(5, Part::A, &example(5, "1")).unwrap();
+    assert_eq!("143", answer.value);
+    assert_eq!("day_05_a = 143", answer.to_string());
+    assert_eq!("123", solve(5, Part::B, &example(5, "1")).unwrap().value);
+}
+
+#[test]
+fn solve_error_test() {
+    let e = solve(9, Part::A, "12x").unwrap_err();
+    assert_eq!((9, Some(1), Some(3)), (e.day, e.line, e.column));
+}
+
+#[test]
+fn model_test() {
+    let updates = day_05::parse(&example(5, "1")).unwrap();
+    assert_eq!(6, updates.len());
+    assert_eq!(
+        vec![75, 47, 61, 53, 29],
+        updates[0].iter().map(|p| p.num).collect::<Vec<_>>()
+    );
+    assert_eq!("day_05_a = 143", day_05::day_05_a(&updates));
+
+    let city = day_08::parse(&example(8, "2")).unwrap();
+    assert_eq!((12, 12), (city.map.rows, city.map.cols));
+    assert_eq!(Some(4), city.antenna_m.get(&'0').map(|ps| ps.len()));
+}
@@ -0,0 +1,53 @@
+// loads the library with dlopen and checks aoc_solve, argv[1] is the library path
+#include <dlfcn.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc2024.h"
+
+typedef int (*solve_fn)(uint32_t, uint32_t, uint32_t, const uint8_t *, size_t, AocBuffer *);
+
+static const char *EXAMPLE = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+static int check(solve_fn solve, uint32_t day, uint32_t part, const char *input, size_t capacity,
+                 int status, const char *expected) {
+    char data[128];
+    AocBuffer out = {data, capacity, 0};
+    int got = solve(AOC_YEAR, day, part, (const uint8_t *)input, strlen(input), &out);
+    if (got != status || (expected && strcmp(data, expected) != 0)) {
+        fprintf(stderr, "day %u part %u: expected %d '%s', got %d '%s'\n", day, part, status,
+                expected ? expected : "", got, got == AOC_ERR_BUFFER ? "" : data);
+        return 1;
+    }
+    printf("day %u part %u: %d %s\n", day, part, got, got == AOC_ERR_BUFFER ? "" : data);
+    return 0;
+}
+
+int main(int argc, char **argv) {
+    if (argc != 2) {
+        fprintf(stderr, "usage: %s LIBRARY\n", argv[0]);
+        return 2;
+    }
+    void *lib = dlopen(argv[1], RTLD_NOW);
+    if (!lib) {
+        fprintf(stderr, "%s\n", dlerror());
+        return 2;
+    }
+    solve_fn solve = (solve_fn)dlsym(lib, "aoc_solve");
+    if (!solve) {
+        fprintf(stderr, "%s\n", dlerror());
+        return 2;
+    }
+
+    int failed = 0;
+    failed += check(solve, 1, 1, EXAMPLE, 128, AOC_OK, "11");
+    failed += check(solve, 1, 2, EXAMPLE, 128, AOC_OK, "31");
+    failed += check(solve, 1, 2, EXAMPLE, 2, AOC_ERR_BUFFER, NULL);
+    failed += check(solve, 1, 1, "3 x\n", 128, AOC_ERR_PARSE,
+                    "day_01 line 1, column 3: invalid number 'x'");
+    failed += check(solve, 30, 1, EXAMPLE, 128, AOC_ERR_DAY, "unknown day 30");
+    failed += check(solve, 12, 1, EXAMPLE, 128, AOC_ERR_DAY, "no solver for day 12");
+
+    dlclose(lib);
+    return failed == 0 ? 0 : 1;
+}
@@ -0,0 +1,20 @@
+use lib::examples::{examples_dir, load_examples, run_example};
+
+#[test]
+fn examples_test() {
+    let examples = load_examples(&examples_dir()).unwrap();
+    for day in 1..=9 {
+        assert!(
+            examples.iter().any(|e| e.day == day),
+            "no examples for day {:02}",
+            day
+        );
+    }
+
+    let checks: Vec<_> = examples.iter().flat_map(run_example).collect();
+    for check in &checks {
+        println!("{}", check);
+    }
+    let failed = checks.iter().filter(|c| !c.passed()).count();
+    assert_eq!(0, failed, "{} example checks failed", failed);
+}
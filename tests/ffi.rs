@@ -0,0 +1,73 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn generate_header() -> String {
+    let dir = manifest_dir();
+    let config = cbindgen::Config::from_file(dir.join("cbindgen.toml")).unwrap();
+    let mut out = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(dir.join("src/ffi.rs"))
+        .with_src(dir.join("src/plugin.rs"))
+        .generate()
+        .unwrap()
+        .write(&mut out);
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn header_up_to_date_test() {
+    let path = manifest_dir().join("include/aoc2024.h");
+    let header = generate_header();
+    if env::var_os("AOC_BLESS").is_some() {
+        fs::write(&path, &header).unwrap();
+    }
+    assert_eq!(
+        fs::read_to_string(&path).unwrap_or_default(),
+        header,
+        "include/aoc2024.h is stale, regenerate it with AOC_BLESS=1 cargo test --test ffi"
+    );
+}
+
+// the cdylib is built next to the test binary in target/<profile>/deps
+fn library_path() -> PathBuf {
+    env::current_exe().unwrap().parent().unwrap().join(format!(
+        "{}lib{}",
+        env::consts::DLL_PREFIX,
+        env::consts::DLL_SUFFIX
+    ))
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn c_program_test() {
+    let library = library_path();
+    assert!(library.exists(), "{} not built", library.display());
+
+    let exe = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("solve_c");
+    let status = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg("-Wall")
+        .arg("-I")
+        .arg(manifest_dir().join("include"))
+        .arg(manifest_dir().join("tests/c/solve.c"))
+        .arg("-o")
+        .arg(&exe)
+        .arg("-ldl")
+        .status()
+        .unwrap();
+    assert!(status.success(), "compiling tests/c/solve.c failed");
+
+    let output = Command::new(&exe).arg(&library).output().unwrap();
+    println!("{}", String::from_utf8_lossy(&output.stdout));
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use lib::leaderboard::{load, Leaderboard};
+
+const BOARD: &str = r#"{"event":"2024","owner_id":7,"members":{
+"7":{"id":7,"name":"owner","stars":2,"local_score":2,"global_score":0,"last_star_ts":1733029300,
+     "completion_day_level":{"1":{"1":{"get_star_ts":1733029260,"star_index":10},"2":{"get_star_ts":1733029300,"star_index":11}}}}
+}}"#;
+
+#[test]
+fn load_test() {
+    let (base_url, server) = common::serve(1, BOARD);
+    let json = load("4242", &base_url, Some("abc")).unwrap();
+    let seen = server.join().unwrap();
+    assert!(seen.contains(&"GET /2024/leaderboard/private/view/4242.json HTTP/1.1".to_string()));
+    assert!(seen
+        .iter()
+        .any(|l| l.eq_ignore_ascii_case("cookie: session=abc")));
+
+    let report = Leaderboard::parse(&json).unwrap().report();
+    assert!(report.contains("owner"));
+    assert!(report.contains("  1        00:01:00     00:01:40     00:00:40"));
+    assert!(!report.contains("differs"));
+}
+
+#[test]
+fn session_origin_test() {
+    let cookie = |seen: &[String]| {
+        seen.iter()
+            .any(|l| l.to_ascii_lowercase().starts_with("cookie:"))
+    };
+
+    // a url on the leaderboard host gets the session
+    let (base_url, server) = common::serve(1, BOARD);
+    load(&format!("{}/board.json", base_url), &base_url, Some("abc")).unwrap();
+    assert!(cookie(&server.join().unwrap()));
+
+    // any other host doesn't
+    let (other_url, server) = common::serve(1, BOARD);
+    load(
+        &format!("{}/board.json", other_url),
+        "https://adventofcode.com",
+        Some("abc"),
+    )
+    .unwrap();
+    assert!(!cookie(&server.join().unwrap()));
+}
@@ -0,0 +1,49 @@
+use lib::fuzzing::PARSERS;
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn arbitrary_text_test(s in any::<String>()) {
+        for (_, parse) in PARSERS.iter() {
+            let _ = parse(&s);
+        }
+    }
+
+    // close to real inputs, so the parsers get past their first checks
+    #[test]
+    fn puzzle_like_text_test(s in r"[0-9 .#^|,:\nXMAS-]{0,200}") {
+        for (_, parse) in PARSERS.iter() {
+            let _ = parse(&s);
+        }
+    }
+}
+
+#[test]
+fn error_position_test() {
+    let err = lib::fuzzing::parse_day_05("47|53\n\n47,99,53").unwrap_err();
+    assert_eq!((3, 4), (err.line, err.column));
+    assert_eq!(
+        "line 3, column 4: page 99 not present in rules",
+        err.to_string()
+    );
+
+    let err = lib::fuzzing::parse_day_06("..#\n...\n").unwrap_err();
+    assert_eq!("no guard '^' in the lab", err.message);
+
+    let err = lib::fuzzing::parse_day_09("23x4").unwrap_err();
+    assert_eq!((1, 3), (err.line, err.column));
+}
+
+#[test]
+fn error_report_test() {
+    let day = lib::days::get_day(5).unwrap();
+    let err = (day.part_a)("47|53\n\n47,99,53").unwrap_err();
+    assert_eq!(
+        "day_05 line 3, column 4: page 99 not present in rules",
+        err.to_string()
+    );
+
+    let day = lib::days::get_day(6).unwrap();
+    let err = (day.part_a)(".#.\n#^#\n.#.").unwrap_err();
+    assert_eq!("day_06: the guard never leaves the lab", err.to_string());
+}
@@ -0,0 +1,81 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use lib::api::{solve, Part};
+use lib::plugin::Plugins;
+
+const INPUT: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+// examples are built in target/<profile>/examples, next to the test binary's deps
+fn target_dir() -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+fn library(dir: &str, name: &str) -> PathBuf {
+    let path = target_dir().join(dir).join(format!(
+        "{}{}{}",
+        env::consts::DLL_PREFIX,
+        name,
+        env::consts::DLL_SUFFIX
+    ));
+    assert!(
+        path.exists(),
+        "{} not built, run cargo build --example plugin_day_01",
+        path.display()
+    );
+    path
+}
+
+#[test]
+fn load_test() {
+    let mut plugins = Plugins::default();
+    let count = plugins.load(&library("examples", "plugin_day_01")).unwrap();
+    assert_eq!(1, count);
+
+    let day = plugins.for_day(1).next().unwrap();
+    assert_eq!("counting", day.name);
+    for part in [Part::A, Part::B] {
+        assert_eq!(
+            solve(1, part, INPUT).unwrap().value,
+            day.solve_part(part, INPUT).unwrap()
+        );
+    }
+    let solution = day.solve(INPUT).unwrap();
+    assert_eq!("day_01_b = 31", solution.part_b);
+
+    // sums past i32 match the built in day 1
+    let big = "2147483647 -2147483648\n2147483647 2147483647\n";
+    for part in [Part::A, Part::B] {
+        assert_eq!(
+            solve(1, part, big).unwrap().value,
+            day.solve_part(part, big).unwrap()
+        );
+    }
+
+    let e = day.solve_part(Part::A, "3 x\n").unwrap_err();
+    assert!(e.to_string().contains("invalid number 'x'"));
+}
+
+#[test]
+fn load_dir_test() {
+    let dir = env::temp_dir().join(format!("aoc_plugin_test_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let plugin = library("examples", "plugin_day_01");
+    fs::copy(&plugin, dir.join(plugin.file_name().unwrap())).unwrap();
+    // the solver library itself exports no plugin entry points
+    let not_plugin = library("deps", "lib");
+    fs::copy(&not_plugin, dir.join(not_plugin.file_name().unwrap())).unwrap();
+    fs::write(dir.join("notes.txt"), "not a library").unwrap();
+
+    let (plugins, failed) = Plugins::load_dir(&dir);
+    assert_eq!(1, plugins.days().len());
+    assert_eq!(1, failed.len());
+    assert!(failed[0].1.to_string().contains("not an aoc2024 plugin"));
+    drop(plugins);
+    fs::remove_dir_all(&dir).unwrap();
+
+    let (plugins, failed) = Plugins::load_dir(&dir);
+    assert!(plugins.is_empty() && failed.is_empty());
+}
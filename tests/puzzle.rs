@@ -0,0 +1,41 @@
+mod common;
+
+use lib::puzzle::{load_page, page_file, render, Style};
+
+const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 3: Stub ---</h2><p>Multiply <em>all</em> the things.</p></article>
+<p>To play, please identify yourself.</p>
+</main></body></html>"#;
+
+#[test]
+fn load_page_test() {
+    let dir = std::env::temp_dir().join(format!("aoc_puzzle_test_{}", std::process::id()));
+    let (base_url, server) = common::serve(2, PAGE);
+
+    // fetched and cached
+    let html = load_page(&dir, &base_url, 3, None, false).unwrap();
+    assert_eq!(PAGE, html);
+    assert_eq!(PAGE, std::fs::read_to_string(page_file(&dir, 3)).unwrap());
+
+    // from the cache, without a session there's no part two to unlock
+    assert_eq!(PAGE, load_page(&dir, &base_url, 3, None, false).unwrap());
+
+    // a session fetches the page again for part two, sending the cookie
+    load_page(&dir, &base_url, 3, Some("abc"), false).unwrap();
+    let seen = server.join().unwrap();
+    assert_eq!(
+        2,
+        seen.iter()
+            .filter(|l| *l == "GET /2024/day/3 HTTP/1.1")
+            .count()
+    );
+    assert!(seen
+        .iter()
+        .any(|l| l.eq_ignore_ascii_case("cookie: session=abc")));
+
+    assert_eq!(
+        "## --- Day 3: Stub ---\n\nMultiply *all* the things.\n",
+        render(&html, Style::Markdown, 80)
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
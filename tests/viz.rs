@@ -0,0 +1,53 @@
+use lib::days::get_day;
+use lib::viz::{Canvas, PATH};
+
+fn draw(day: u8, name: &str) -> Canvas {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/data/examples");
+    let input = std::fs::read_to_string(format!("{}/day_{:02}/{}.txt", dir, day, name)).unwrap();
+    let visualize = get_day(day).unwrap().visualize.unwrap();
+    visualize(&input).unwrap()
+}
+
+#[test]
+fn day_04_test() {
+    let canvas = draw(4, "1");
+    let highlighted = (0..canvas.rows)
+        .flat_map(|r| (0..canvas.cols).map(move |c| (r, c)))
+        .filter(|&(r, c)| canvas.get(r, c).color == PATH)
+        .count();
+    assert_eq!((10, 10), (canvas.rows, canvas.cols));
+    // 18 XMAS share some of their letters
+    assert_eq!(54, highlighted);
+}
+
+#[test]
+fn day_06_test() {
+    let canvas = draw(6, "1");
+    // 41 visited cells, one is the start and 6 take an obstruction
+    assert_eq!(
+        (34, 6, 1),
+        (canvas.count('X'), canvas.count('O'), canvas.count('^'))
+    );
+}
+
+#[test]
+fn day_08_test() {
+    let canvas = draw(8, "2");
+    // 14 part a antinodes, one of them under an 'A' antenna
+    assert_eq!(13, canvas.count('#'));
+}
+
+#[test]
+fn day_09_test() {
+    let lines = draw(9, "1").lines();
+    assert_eq!(
+        "00...111...2...333.44.5555.6666.777.888899",
+        lines[0].trim_end()
+    );
+    assert_eq!("00992111777.44.333....5555.6666.....8888..", lines[2]);
+}
+
+#[test]
+fn no_visualize_test() {
+    assert!(get_day(1).unwrap().visualize.is_none());
+}